@@ -0,0 +1,26 @@
+use bytes_text::Text;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+fn count_byte(c: &mut Criterion) {
+    let line = "a line of log output, with some ünïcode in it\n";
+    let text = Text::from(line.repeat(10 * 1024 * 1024 / line.len()));
+
+    let mut group = c.benchmark_group("count_byte");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("count_byte", |b| {
+        b.iter(|| black_box(&text).count_byte(b'\n'))
+    });
+    group.bench_function("naive", |b| {
+        b.iter(|| {
+            black_box(&text)
+                .as_bytes()
+                .iter()
+                .filter(|&&b| b == b'\n')
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, count_byte);
+criterion_main!(benches);
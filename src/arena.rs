@@ -0,0 +1,131 @@
+use bytes::BytesMut;
+
+use crate::Text;
+
+/// The default number of bytes allocated at a time by [`TextArena`]
+const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+/// Allocates many small `Text`s out of a few large, shared buffers
+///
+/// Rather than making one allocation per `Text`, the arena copies each string
+/// into a shared chunk of memory and hands out a `Text` pointing into it. A
+/// chunk is freed once every `Text` pointing into it has been dropped, so this
+/// works best for values that all die together, such as everything parsed out
+/// of a single request.
+///
+/// Strings larger than the chunk size get a chunk of their own.
+///
+/// # Example
+///
+/// ```
+/// # use bytes_text::TextArena;
+/// let mut arena = TextArena::new();
+/// let method = arena.copy_from("GET");
+/// let path = arena.copy_from("/index.html");
+/// assert_eq!(method, "GET");
+/// assert_eq!(path, "/index.html");
+///
+/// // Both point into the same allocation
+/// assert_eq!(method.as_ptr().wrapping_add(method.len()), path.as_ptr());
+/// ```
+pub struct TextArena {
+    chunk: BytesMut,
+    chunk_size: usize,
+}
+
+impl TextArena {
+    /// Creates a new arena that allocates 4 KiB at a time
+    ///
+    /// No memory is allocated until the first string is copied in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextArena;
+    /// let mut arena = TextArena::new();
+    /// let text = arena.copy_from("Hello!");
+    /// ```
+    pub fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates a new arena that allocates `chunk_size` bytes at a time
+    ///
+    /// No memory is allocated until the first string is copied in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextArena;
+    /// let mut arena = TextArena::with_chunk_size(64);
+    /// let text = arena.copy_from("Hello!");
+    /// ```
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            chunk: BytesMut::new(),
+            chunk_size,
+        }
+    }
+
+    /// Copies the provided string into the arena
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextArena;
+    /// let mut arena = TextArena::new();
+    /// let s = format!("the answer is: {}", 42);
+    /// let text = arena.copy_from(s);
+    /// assert_eq!(text, "the answer is: 42");
+    /// ```
+    pub fn copy_from(&mut self, s: impl AsRef<str>) -> Text {
+        let s = s.as_ref();
+        if self.chunk.capacity() < s.len() {
+            // Start a new chunk rather than letting `BytesMut` grow this one,
+            // since the old one is still shared with previous `Text`s
+            self.chunk = BytesMut::with_capacity(self.chunk_size.max(s.len()));
+        }
+        self.chunk.extend_from_slice(s.as_bytes());
+        // Safety: the chunk is always empty before the string is copied in,
+        // so this only contains the valid UTF-8 from `s`
+        unsafe { Text::from_utf8_unchecked(self.chunk.split().freeze()) }
+    }
+}
+
+impl Default for TextArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_arena_shares_chunks() {
+        let mut arena = TextArena::with_chunk_size(16);
+        let a = arena.copy_from("héllo");
+        let b = arena.copy_from("wörld");
+        assert_eq!(a, "héllo");
+        assert_eq!(b, "wörld");
+        // contiguous in the same chunk
+        assert_eq!(a.as_ptr().wrapping_add(a.len()), b.as_ptr());
+
+        // doesn't fit in the rest of the chunk, so a new one is started
+        let c = arena.copy_from("0123456789");
+        assert_eq!(c, "0123456789");
+        assert_ne!(b.as_ptr().wrapping_add(b.len()), c.as_ptr());
+
+        // bigger than a whole chunk
+        let big = "🦀".repeat(10);
+        let d = arena.copy_from(&big);
+        assert_eq!(d, big);
+        assert_eq!(arena.copy_from(""), "");
+
+        // earlier texts are unaffected by the later chunks
+        drop(arena);
+        assert_eq!(a, "héllo");
+        assert_eq!(b, "wörld");
+    }
+}
@@ -0,0 +1,170 @@
+use core::ops::Deref;
+
+use crate::Text;
+
+/// A view of a [`Text`] that is known to be ASCII
+///
+/// Since every byte of ASCII text is a whole char, byte offsets and char
+/// indices are the same thing, and every offset is a char boundary. This
+/// lets slicing and indexing skip the char boundary checks that `Text`
+/// needs.
+///
+/// Created by [`Text::as_ascii`]
+///
+/// # Example
+///
+/// ```
+/// # use bytes_text::Text;
+/// let text = Text::from("GET /index.html");
+/// let ascii = text.as_ascii().unwrap();
+/// assert_eq!(ascii.byte_at(0), b'G');
+/// assert_eq!(ascii.char_at(4), '/');
+/// assert_eq!(ascii.slice(4, 15), "/index.html");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct AsciiText<'a>(&'a Text);
+
+impl<'a> AsciiText<'a> {
+    pub(crate) fn new(text: &'a Text) -> Option<Self> {
+        soft_assert::soft_assert!(text.is_ascii());
+        Some(Self(text))
+    }
+
+    /// The text this is a view of
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("ascii");
+    /// let ascii = text.as_ascii().unwrap();
+    /// assert_eq!(ascii.as_text(), &text);
+    /// ```
+    pub fn as_text(&self) -> &'a Text {
+        self.0
+    }
+
+    /// Gets the byte at `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("ascii");
+    /// let ascii = text.as_ascii().unwrap();
+    /// assert_eq!(ascii.byte_at(1), b's');
+    /// ```
+    pub fn byte_at(&self, index: usize) -> u8 {
+        self.0.as_bytes()[index]
+    }
+
+    /// Gets the char at `index`, which is the same as the byte at `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("ascii");
+    /// let ascii = text.as_ascii().unwrap();
+    /// assert_eq!(ascii.char_at(1), 's');
+    /// ```
+    pub fn char_at(&self, index: usize) -> char {
+        self.byte_at(index) as char
+    }
+
+    /// Gets a sub-body of text, from byte (and char) `start` up to `end`
+    ///
+    /// This doesn't need to check for char boundaries, and doesn't copy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start > end` or `end > len`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("ascii only");
+    /// let ascii = text.as_ascii().unwrap();
+    /// assert_eq!(ascii.slice(6, 10), "only");
+    /// ```
+    pub fn slice(&self, start: usize, end: usize) -> Text {
+        assert!(
+            start <= end,
+            "slice start {} is past its end {}",
+            start,
+            end
+        );
+        assert!(end <= self.0.len(), "slice end {} is out of bounds", end);
+        // Safety: every offset into ASCII text is a char boundary
+        unsafe { self.0.subtext_unchecked(start, end) }
+    }
+
+    /// Splits the text into two halves at byte (and char) `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("key=value");
+    /// let ascii = text.as_ascii().unwrap();
+    /// let (key, value) = ascii.split_at(3);
+    /// assert_eq!(key, "key");
+    /// assert_eq!(value, "=value");
+    /// ```
+    pub fn split_at(&self, index: usize) -> (Text, Text) {
+        (self.slice(0, index), self.slice(index, self.0.len()))
+    }
+}
+
+impl Deref for AsciiText<'_> {
+    type Target = Text;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_text() {
+        assert!(Text::from("naïve").as_ascii().is_none());
+        assert!(Text::from("🦀").as_ascii().is_none());
+        assert!(Text::new().as_ascii().is_some());
+
+        let text = Text::from("Hello, world!\n");
+        let ascii = text.as_ascii().unwrap();
+        assert_eq!(ascii.len(), 14);
+        for i in 0..ascii.len() {
+            assert_eq!(ascii.char_at(i), text.chars().nth(i).unwrap());
+            assert_eq!(ascii.slice(i, ascii.len()), text.get(i..).unwrap());
+            assert_eq!(ascii.slice(0, i), text.get(..i).unwrap());
+        }
+        assert_eq!(ascii.slice(7, 12).as_ptr(), text[7..].as_ptr());
+        let (a, b) = ascii.split_at(5);
+        assert_eq!(a, "Hello");
+        assert_eq!(b, ", world!\n");
+    }
+
+    #[test]
+    #[should_panic]
+    fn ascii_text_slice_out_of_bounds() {
+        let text = Text::from("short");
+        text.as_ascii().unwrap().slice(2, 6);
+    }
+}
@@ -0,0 +1,155 @@
+use core::ops::Deref;
+
+use crate::Text;
+
+/// A [`Text`] along with its number of chars and lines, counted up front
+///
+/// Counting the chars of a `Text` takes O(n) time, so code that asks for it
+/// over and over on the same text can count once with this instead. Since
+/// the text can't change, the counts never go stale. Anything sliced out of
+/// it (through [`Deref`] to `Text`) is a plain `Text`, not a `CountedText`.
+///
+/// # Example
+///
+/// ```
+/// # use bytes_text::{CountedText, Text};
+/// let counted = CountedText::new(Text::from("año\nñu"));
+/// assert_eq!(counted.char_len(), 6);
+/// assert_eq!(counted.line_count(), 2);
+/// assert_eq!(counted.len(), 8);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CountedText {
+    text: Text,
+    char_len: usize,
+    line_count: usize,
+}
+
+impl CountedText {
+    /// Counts the chars and lines of `text`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::{CountedText, Text};
+    /// let counted = CountedText::new(Text::from("🌍"));
+    /// assert_eq!(counted.char_len(), 1);
+    /// ```
+    pub fn new(text: Text) -> Self {
+        let char_len = text.chars().count();
+        // lines are split like `str::lines`, so a final line ending doesn't
+        // start another line
+        let line_count = text.count_byte(b'\n') + usize::from(!text.ends_with('\n'))
+            - usize::from(text.is_empty());
+        Self {
+            text,
+            char_len,
+            line_count,
+        }
+    }
+
+    /// The number of chars in the text
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::{CountedText, Text};
+    /// let counted = CountedText::new(Text::from("naïve"));
+    /// assert_eq!(counted.char_len(), 5);
+    /// ```
+    pub fn char_len(&self) -> usize {
+        self.char_len
+    }
+
+    /// The number of lines in the text, as counted by [`str::lines`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::{CountedText, Text};
+    /// let counted = CountedText::new(Text::from("one\r\ntwo\n"));
+    /// assert_eq!(counted.line_count(), 2);
+    /// ```
+    pub fn line_count(&self) -> usize {
+        self.line_count
+    }
+
+    /// The text that was counted
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::{CountedText, Text};
+    /// let text = Text::from("counted");
+    /// let counted = CountedText::new(text.clone());
+    /// assert_eq!(counted.as_text(), &text);
+    /// ```
+    pub fn as_text(&self) -> &Text {
+        &self.text
+    }
+
+    /// Converts back into the text that was counted
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::{CountedText, Text};
+    /// let counted = CountedText::new(Text::from("counted"));
+    /// let text: Text = counted.into_text();
+    /// assert_eq!(text, "counted");
+    /// ```
+    pub fn into_text(self) -> Text {
+        self.text
+    }
+}
+
+impl Deref for CountedText {
+    type Target = Text;
+
+    fn deref(&self) -> &Text {
+        &self.text
+    }
+}
+
+impl From<Text> for CountedText {
+    fn from(text: Text) -> Self {
+        Self::new(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts() {
+        let texts = [
+            "",
+            "ascii",
+            "ünïcödé 🌍",
+            "one\ntwo\r\nthree",
+            "trailing\n",
+            "\n\n",
+            "\r\n",
+        ];
+        for s in texts {
+            let counted = CountedText::new(Text::from(s));
+            assert_eq!(counted.char_len(), s.chars().count(), "{:?}", s);
+            assert_eq!(counted.line_count(), s.lines().count(), "{:?}", s);
+            assert_eq!(counted.as_text(), s);
+        }
+    }
+
+    #[test]
+    fn slicing_gives_text() {
+        let counted = CountedText::from(Text::from("héllo wörld"));
+        let slice: Text = counted.get(..6).unwrap();
+        assert_eq!(slice, "héllo");
+        assert_eq!(counted.char_len(), 11);
+
+        // counting the slice gives fresh counts
+        let recounted = CountedText::new(slice);
+        assert_eq!(recounted.char_len(), 5);
+        assert_eq!(recounted.line_count(), 1);
+    }
+}
@@ -0,0 +1,190 @@
+use core::str::Utf8Error;
+
+use bytes::{Bytes, BytesMut};
+
+use crate::Text;
+
+/// Decodes UTF-8 text that arrives in chunks, such as reads from a socket
+///
+/// A chunk may end partway through a multibyte char. Rather than treating
+/// that as an error, the decoder holds on to the incomplete bytes (at most 3)
+/// and puts them in front of the next chunk. Chunks that don't need this are
+/// wrapped without copying.
+///
+/// # Example
+///
+/// ```
+/// # use bytes_text::Utf8Decoder;
+/// # use bytes::Bytes;
+/// let mut decoder = Utf8Decoder::new();
+/// // "🦀" is split across the two chunks
+/// let first = decoder.push(Bytes::from_static(b"crab \xf0\x9f")).unwrap();
+/// assert_eq!(first, "crab ");
+/// let second = decoder.push(Bytes::from_static(b"\xa6\x80!")).unwrap();
+/// assert_eq!(second, "🦀!");
+/// assert_eq!(decoder.finish().unwrap(), "");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Utf8Decoder {
+    /// The start of a char cut off at the end of the last chunk
+    pending: BytesMut,
+}
+
+impl Utf8Decoder {
+    /// Creates a new decoder with nothing pending
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Utf8Decoder;
+    /// let decoder = Utf8Decoder::new();
+    /// assert!(!decoder.has_pending());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decodes as much of `chunk` as possible
+    ///
+    /// Any bytes left over from the last chunk are decoded first. If `chunk`
+    /// ends partway through a char, those bytes are held back until the next
+    /// call, so the returned text may be shorter than `chunk`, or even empty.
+    ///
+    /// Returns an error if the input contains an invalid sequence. The
+    /// positions in the error count from the first left over byte, if there
+    /// were any. After an error, anything pending is discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Utf8Decoder;
+    /// # use bytes::Bytes;
+    /// let mut decoder = Utf8Decoder::new();
+    /// assert_eq!(decoder.push(Bytes::from_static(b"ok")).unwrap(), "ok");
+    /// assert!(decoder.push(Bytes::from_static(b"\xc3\x28")).is_err());
+    /// ```
+    pub fn push(&mut self, chunk: Bytes) -> Result<Text, Utf8Error> {
+        let chunk = if self.pending.is_empty() {
+            chunk
+        } else {
+            let mut buf = self.pending.split();
+            buf.extend_from_slice(&chunk);
+            buf.freeze()
+        };
+        match core::str::from_utf8(&chunk) {
+            // Safety: `chunk` was just checked to be valid UTF-8
+            Ok(_) => Ok(unsafe { Text::from_utf8_unchecked(chunk) }),
+            // the chunk ends partway through a char
+            Err(e) if e.error_len().is_none() => {
+                let valid = e.valid_up_to();
+                self.pending.extend_from_slice(&chunk[valid..]);
+                // Safety: everything up to `valid` is valid UTF-8
+                Ok(unsafe { Text::from_utf8_unchecked(chunk.slice(..valid)) })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Whether the last chunk ended partway through a char
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Utf8Decoder;
+    /// # use bytes::Bytes;
+    /// let mut decoder = Utf8Decoder::new();
+    /// decoder.push(Bytes::from_static(b"\xc3")).unwrap();
+    /// assert!(decoder.has_pending());
+    /// ```
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Finishes decoding, checking that the input didn't end partway through
+    /// a char
+    ///
+    /// Since every complete char has already been returned by
+    /// [`Utf8Decoder::push`], the text returned here is always empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Utf8Decoder;
+    /// # use bytes::Bytes;
+    /// let mut decoder = Utf8Decoder::new();
+    /// decoder.push(Bytes::from_static(b"cut off \xe2\x82")).unwrap();
+    /// assert!(decoder.finish().is_err());
+    /// ```
+    pub fn finish(self) -> Result<Text, Utf8Error> {
+        core::str::from_utf8(&self.pending)?;
+        Ok(Text::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(chunks: &[&'static [u8]]) -> Result<String, Utf8Error> {
+        let mut decoder = Utf8Decoder::new();
+        let mut out = String::new();
+        for chunk in chunks {
+            out.push_str(&decoder.push(Bytes::from_static(chunk))?);
+        }
+        out.push_str(&decoder.finish()?);
+        Ok(out)
+    }
+
+    #[test]
+    fn split_chars() {
+        let crab = "🦀".as_bytes();
+        assert_eq!(
+            decode(&[b"a", &crab[..2], &crab[2..], b"b"]).unwrap(),
+            "a🦀b"
+        );
+        assert_eq!(
+            decode(&[&crab[..1], &crab[1..3], &crab[3..]]).unwrap(),
+            "🦀"
+        );
+        assert_eq!(
+            decode(&[&crab[..1], &crab[1..2], &crab[2..3], &crab[3..]]).unwrap(),
+            "🦀"
+        );
+        assert_eq!(decode(&[b"\xc3", b"\xa9t\xc3", b"\xa9"]).unwrap(), "été");
+        assert_eq!(decode(&[b"", b"plain", b""]).unwrap(), "plain");
+    }
+
+    #[test]
+    fn whole_chunks_are_not_copied() {
+        let chunk = Bytes::from_static("ünïcode".as_bytes());
+        let mut decoder = Utf8Decoder::new();
+        let text = decoder.push(chunk.clone()).unwrap();
+        assert_eq!(text.as_ptr(), chunk.as_ptr());
+
+        // only the part before a cut off char is returned, still uncopied
+        let chunk = Bytes::from_static(b"abc\xe2\x82");
+        let text = decoder.push(chunk.clone()).unwrap();
+        assert_eq!(text, "abc");
+        assert_eq!(text.as_ptr(), chunk.as_ptr());
+        assert!(decoder.has_pending());
+        assert_eq!(decoder.push(Bytes::from_static(b"\xac")).unwrap(), "€");
+        assert!(!decoder.has_pending());
+    }
+
+    #[test]
+    fn invalid_sequences() {
+        // a lead byte followed by something other than a continuation byte
+        let err = decode(&[b"ab\xf0\x9f", b"x"]).unwrap_err();
+        assert_eq!(err.valid_up_to(), 0);
+        assert_eq!(err.error_len(), Some(2));
+
+        let err = decode(&[b"ok", b"\x80"]).unwrap_err();
+        assert_eq!(err.valid_up_to(), 0);
+        assert!(decode(&[b"\xff"]).is_err());
+
+        // the input ends partway through a char
+        let err = decode(&[b"ok\xf0\x9f\xa6"]).unwrap_err();
+        assert_eq!(err.valid_up_to(), 0);
+        assert_eq!(err.error_len(), None);
+    }
+}
@@ -0,0 +1,197 @@
+use alloc::string::String;
+use core::{
+    fmt::{Debug, Display, Write},
+    str::Utf8Error,
+};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use bytes::Bytes;
+
+/// The number of bytes of context shown on each side of an invalid sequence
+/// in a [`Utf8Diagnostic`] snippet
+const SNIPPET_CONTEXT: usize = 4;
+
+/// A UTF-8 validation error with extra context for debugging bad input
+///
+/// Returned by [`Text::from_utf8_diagnostic`](crate::Text::from_utf8_diagnostic)
+///
+/// # Example
+///
+/// ```
+/// # use bytes_text::Text;
+/// # use bytes::Bytes;
+/// let buf = Bytes::from_static(b"hello\xffworld");
+/// let err = Text::from_utf8_diagnostic(buf).unwrap_err();
+/// assert_eq!(err.valid_up_to(), 5);
+/// assert_eq!(err.invalid_bytes(), b"\xff");
+/// assert_eq!(err.snippet(), "65 6c 6c 6f [ff] 77 6f 72 6c");
+/// ```
+#[derive(Clone)]
+pub struct Utf8Diagnostic {
+    error: Utf8Error,
+    invalid: Bytes,
+    snippet: String,
+}
+
+impl Utf8Diagnostic {
+    pub(crate) fn new(bytes: &Bytes, error: Utf8Error) -> Self {
+        let start = error.valid_up_to();
+        let end = match error.error_len() {
+            Some(len) => start + len,
+            // the input ended partway through a sequence
+            None => bytes.len(),
+        };
+        let before = start.saturating_sub(SNIPPET_CONTEXT);
+        let after = (end + SNIPPET_CONTEXT).min(bytes.len());
+
+        let mut snippet = String::new();
+        for (i, b) in bytes[before..after].iter().enumerate() {
+            let i = before + i;
+            if i > before {
+                snippet.push(' ');
+            }
+            if i == start {
+                snippet.push('[');
+            }
+            // writing to a `String` can't fail
+            let _ = write!(snippet, "{:02x}", b);
+            if i + 1 == end {
+                snippet.push(']');
+            }
+        }
+
+        Self {
+            error,
+            invalid: bytes.slice(start..end),
+            snippet,
+        }
+    }
+
+    /// The index in the input up to which valid UTF-8 was verified
+    ///
+    /// See [`Utf8Error::valid_up_to`]
+    pub fn valid_up_to(&self) -> usize {
+        self.error.valid_up_to()
+    }
+
+    /// The bytes that make up the invalid sequence
+    ///
+    /// If the input ended partway through a sequence, this is everything after
+    /// [`Utf8Diagnostic::valid_up_to`].
+    pub fn invalid_bytes(&self) -> &[u8] {
+        &self.invalid
+    }
+
+    /// A hex rendering of the bytes around the invalid sequence, with the
+    /// invalid bytes in square brackets
+    ///
+    /// Up to 4 bytes are shown on each side.
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+
+    /// The underlying `Utf8Error`
+    pub fn utf8_error(&self) -> Utf8Error {
+        self.error
+    }
+}
+
+impl Display for Utf8Diagnostic {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "invalid utf-8 sequence at byte {}: {}",
+            self.valid_up_to(),
+            self.snippet
+        )
+    }
+}
+
+impl Debug for Utf8Diagnostic {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Utf8Diagnostic")
+            .field("valid_up_to", &self.valid_up_to())
+            .field("invalid_bytes", &self.invalid)
+            .field("snippet", &self.snippet)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Utf8Diagnostic {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// A UTF-8 validation error that hands back the buffer that failed to convert
+///
+/// Returned by [`Text::from_utf8`](crate::Text::from_utf8) (with `B` as
+/// `Bytes`) and [`TextMut::from_utf8`](crate::TextMut::from_utf8) (with `B` as
+/// `BytesMut`). This mirrors [`std::string::FromUtf8Error`].
+///
+/// # Example
+///
+/// ```
+/// # use bytes_text::Text;
+/// # use bytes::Bytes;
+/// let buf = Bytes::from_static(b"caf\xe9");
+/// let err = Text::from_utf8(buf).unwrap_err();
+/// assert_eq!(err.utf8_error().valid_up_to(), 3);
+///
+/// // the buffer isn't lost, so it can still be decoded some other way
+/// let text = Text::from_utf8_lossy(err.into_bytes());
+/// assert_eq!(text, "caf\u{FFFD}");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct FromUtf8Error<B = Bytes> {
+    bytes: B,
+    error: Utf8Error,
+}
+
+impl<B> FromUtf8Error<B> {
+    pub(crate) fn new(bytes: B, error: Utf8Error) -> Self {
+        Self { bytes, error }
+    }
+
+    /// The bytes that failed to convert
+    pub fn as_bytes(&self) -> &[u8]
+    where
+        B: AsRef<[u8]>,
+    {
+        self.bytes.as_ref()
+    }
+
+    /// Gives back the buffer that failed to convert, unchanged
+    pub fn into_bytes(self) -> B {
+        self.bytes
+    }
+
+    /// The underlying `Utf8Error`
+    pub fn utf8_error(&self) -> Utf8Error {
+        self.error
+    }
+}
+
+impl<B> Display for FromUtf8Error<B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl<B: Debug> Debug for FromUtf8Error<B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FromUtf8Error")
+            .field("bytes", &self.bytes)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<B: Debug> Error for FromUtf8Error<B> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
@@ -0,0 +1,1055 @@
+use core::iter::FusedIterator;
+
+use crate::{Pattern, Text, TextMut};
+
+/// An iterator over the quoted fields of a [`Text`]
+///
+/// Created by [`Text::quoted_fields`]
+#[derive(Clone, Debug)]
+pub struct QuotedFields {
+    text: Text,
+    pos: usize,
+    delim: char,
+    quote: char,
+    finished: bool,
+}
+
+impl QuotedFields {
+    pub(crate) fn new(text: Text, delim: char, quote: char) -> Self {
+        Self {
+            text,
+            pos: 0,
+            delim,
+            quote,
+            finished: false,
+        }
+    }
+
+    /// Finds the end of the field starting at `start`, and moves past the
+    /// delimiter after it
+    fn advance(&mut self, start: usize) -> usize {
+        match self.text[start..].find(self.delim) {
+            Some(i) => {
+                self.pos = start + i + self.delim.len_utf8();
+                start + i
+            }
+            None => {
+                self.finished = true;
+                self.text.len()
+            }
+        }
+    }
+}
+
+impl Iterator for QuotedFields {
+    type Item = Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let start = self.pos;
+        if !self.text[start..].starts_with(self.quote) {
+            let end = self.advance(start);
+            // Safety: `start` and `end` are either the ends of the text, or
+            // next to a delimiter
+            return Some(unsafe { self.text.subtext_unchecked(start, end) });
+        }
+
+        let quote_len = self.quote.len_utf8();
+        let inner_start = start + quote_len;
+        // only needed once a doubled quote is found
+        let mut unescaped: Option<TextMut> = None;
+        let mut chunk_start = inner_start;
+        let mut i = inner_start;
+        let inner_end = loop {
+            let q = match self.text[i..].find(self.quote) {
+                Some(q) => i + q,
+                // never closed, so the rest of the text is in the field
+                None => {
+                    i = self.text.len();
+                    break i;
+                }
+            };
+            let after = q + quote_len;
+            if self.text[after..].starts_with(self.quote) {
+                // keep the first of the two quotes
+                let buf = unescaped.get_or_insert_with(TextMut::new);
+                buf.push_str(&self.text[chunk_start..after]);
+                chunk_start = after + quote_len;
+                i = chunk_start;
+            } else {
+                i = after;
+                break q;
+            }
+        };
+
+        let trailing_start = i;
+        let trailing_end = self.advance(trailing_start);
+        let trailing = &self.text[trailing_start..trailing_end];
+        match unescaped {
+            // Safety: `inner_start` is right after a quote, and `inner_end` is
+            // either the end of the text or right before a quote
+            None if trailing.is_empty() => {
+                Some(unsafe { self.text.subtext_unchecked(inner_start, inner_end) })
+            }
+            unescaped => {
+                let mut buf = unescaped.unwrap_or_default();
+                buf.push_str(&self.text[chunk_start..inner_end]);
+                buf.push_str(trailing);
+                Some(buf.freeze())
+            }
+        }
+    }
+}
+
+impl FusedIterator for QuotedFields {}
+
+/// An iterator over the lines of a [`Text`], along with their line endings
+///
+/// Created by [`Text::lines_with_endings`]
+#[derive(Clone, Debug)]
+pub struct LinesWithEndings {
+    text: Text,
+    pos: usize,
+    end: usize,
+}
+
+impl LinesWithEndings {
+    pub(crate) fn new(text: Text) -> Self {
+        let end = text.len();
+        Self { text, pos: 0, end }
+    }
+
+    /// Gets the line between `start` and `content_end`, and the line ending
+    /// between `content_end` and `end`
+    ///
+    /// # Safety
+    ///
+    /// All of these must be char boundaries, in order
+    unsafe fn line(&self, start: usize, content_end: usize, end: usize) -> (Text, Text) {
+        (
+            self.text.subtext_unchecked(start, content_end),
+            self.text.subtext_unchecked(content_end, end),
+        )
+    }
+}
+
+impl Iterator for LinesWithEndings {
+    type Item = (Text, Text);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.pos;
+        if start >= self.end {
+            return None;
+        }
+        let (content_end, end) = match self.text[start..self.end].find('\n') {
+            Some(i) => {
+                let newline = start + i;
+                if self.text[start..newline].ends_with('\r') {
+                    (newline - 1, newline + 1)
+                } else {
+                    (newline, newline + 1)
+                }
+            }
+            None => (self.end, self.end),
+        };
+        self.pos = end;
+        // Safety: all of these are either the ends of the remaining text, or
+        // next to an ASCII `\r` or `\n`
+        unsafe { Some(self.line(start, content_end, end)) }
+    }
+}
+
+impl DoubleEndedIterator for LinesWithEndings {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let end = self.end;
+        if self.pos >= end {
+            return None;
+        }
+        let remaining = &self.text[self.pos..end];
+        let (content, content_end) = match remaining.strip_suffix('\n') {
+            Some(content) => match content.strip_suffix('\r') {
+                Some(content) => (content, end - 2),
+                None => (content, end - 1),
+            },
+            None => (remaining, end),
+        };
+        let start = match content.rfind('\n') {
+            Some(i) => self.pos + i + 1,
+            None => self.pos,
+        };
+        self.end = start;
+        // Safety: all of these are either the ends of the remaining text, or
+        // next to an ASCII `\r` or `\n`
+        unsafe { Some(self.line(start, content_end, end)) }
+    }
+}
+
+impl FusedIterator for LinesWithEndings {}
+
+/// An iterator over the lines of a [`Text`]
+///
+/// Created by [`Text::lines`]
+#[derive(Clone, Debug)]
+pub struct Lines(LinesWithEndings);
+
+impl Lines {
+    pub(crate) fn new(text: Text) -> Self {
+        Self(LinesWithEndings::new(text))
+    }
+}
+
+impl Iterator for Lines {
+    type Item = Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(line, _)| line)
+    }
+}
+
+impl DoubleEndedIterator for Lines {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(line, _)| line)
+    }
+}
+
+impl FusedIterator for Lines {}
+
+/// An iterator over the parts of a [`Text`] separated by a pattern
+///
+/// Created by [`Text::split`]
+#[derive(Clone, Debug)]
+pub struct Split<P> {
+    matches: MatchIndices<P>,
+    start: usize,
+    finished: bool,
+}
+
+impl<P: Pattern> Split<P> {
+    pub(crate) fn new(text: Text, delim: P) -> Self {
+        Self {
+            matches: MatchIndices::new(text, delim),
+            start: 0,
+            finished: false,
+        }
+    }
+
+    /// Gets everything that hasn't been yielded yet, and finishes the
+    /// iterator
+    fn remainder(&mut self) -> Option<Text> {
+        if self.finished {
+            return None;
+        }
+        self.finished = true;
+        let text = &self.matches.text;
+        // Safety: `start` is either the start of the text, or right after a
+        // delimiter
+        Some(unsafe { text.subtext_unchecked(self.start, text.len()) })
+    }
+}
+
+impl<P: Pattern> Iterator for Split<P> {
+    type Item = Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.matches.next_range() {
+            Some((end, next_start)) => {
+                let start = self.start;
+                self.start = next_start;
+                // Safety: `start` is either the start of the text or right
+                // after a delimiter, and `end` is right before one
+                Some(unsafe { self.matches.text.subtext_unchecked(start, end) })
+            }
+            None => self.remainder(),
+        }
+    }
+}
+
+impl<P: Pattern> FusedIterator for Split<P> {}
+
+/// An iterator over at most `n` parts of a [`Text`] separated by a pattern
+///
+/// Created by [`Text::splitn`]
+#[derive(Clone, Debug)]
+pub struct SplitN<P> {
+    inner: Split<P>,
+    remaining: usize,
+}
+
+impl<P: Pattern> SplitN<P> {
+    pub(crate) fn new(text: Text, n: usize, delim: P) -> Self {
+        Self {
+            inner: Split::new(text, delim),
+            remaining: n,
+        }
+    }
+}
+
+impl<P: Pattern> Iterator for SplitN<P> {
+    type Item = Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.remaining {
+            0 => None,
+            1 => {
+                self.remaining = 0;
+                self.inner.remainder()
+            }
+            _ => {
+                self.remaining -= 1;
+                self.inner.next()
+            }
+        }
+    }
+}
+
+impl<P: Pattern> FusedIterator for SplitN<P> {}
+
+/// An iterator over the parts of a [`Text`] separated by a pattern, starting
+/// from the end
+///
+/// Created by [`Text::rsplit`]
+#[derive(Clone, Debug)]
+pub struct RSplit<P> {
+    text: Text,
+    /// The end of the next part
+    end: usize,
+    /// Where to search back from for the next delimiter
+    search_end: usize,
+    delim: P,
+    /// Whether there are no delimiters left, only the first part
+    delims_done: bool,
+    finished: bool,
+}
+
+impl<P: Pattern> RSplit<P> {
+    pub(crate) fn new(text: Text, delim: P) -> Self {
+        let end = text.len();
+        Self {
+            text,
+            end,
+            search_end: end,
+            delim,
+            delims_done: false,
+            finished: false,
+        }
+    }
+
+    /// Gets everything that hasn't been yielded yet, and finishes the
+    /// iterator
+    fn remainder(&mut self) -> Option<Text> {
+        if self.finished {
+            return None;
+        }
+        self.finished = true;
+        // Safety: `end` is either the end of the text, or right before a
+        // delimiter
+        Some(unsafe { self.text.subtext_unchecked(0, self.end) })
+    }
+}
+
+impl<P: Pattern> Iterator for RSplit<P> {
+    type Item = Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let found = if self.delims_done {
+            None
+        } else {
+            self.delim.next_match_back(&self.text[..self.search_end])
+        };
+        match found {
+            Some((start, delim_end)) => {
+                let end = self.end;
+                self.end = start;
+                if start == delim_end {
+                    // an empty delimiter can match at every char boundary, so
+                    // step back over the previous char to avoid matching at
+                    // the same place forever
+                    match self.text[..start].chars().next_back() {
+                        Some(c) => self.search_end = start - c.len_utf8(),
+                        None => self.delims_done = true,
+                    }
+                } else {
+                    self.search_end = start;
+                }
+                // Safety: `delim_end` is right after a delimiter, and `end` is
+                // either the end of the text or right before one
+                Some(unsafe { self.text.subtext_unchecked(delim_end, end) })
+            }
+            None => self.remainder(),
+        }
+    }
+}
+
+impl<P: Pattern> FusedIterator for RSplit<P> {}
+
+/// An iterator over at most `n` parts of a [`Text`] separated by a pattern,
+/// starting from the end
+///
+/// Created by [`Text::rsplitn`]
+#[derive(Clone, Debug)]
+pub struct RSplitN<P> {
+    inner: RSplit<P>,
+    remaining: usize,
+}
+
+impl<P: Pattern> RSplitN<P> {
+    pub(crate) fn new(text: Text, n: usize, delim: P) -> Self {
+        Self {
+            inner: RSplit::new(text, delim),
+            remaining: n,
+        }
+    }
+}
+
+impl<P: Pattern> Iterator for RSplitN<P> {
+    type Item = Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.remaining {
+            0 => None,
+            1 => {
+                self.remaining = 0;
+                self.inner.remainder()
+            }
+            _ => {
+                self.remaining -= 1;
+                self.inner.next()
+            }
+        }
+    }
+}
+
+impl<P: Pattern> FusedIterator for RSplitN<P> {}
+
+/// An iterator over the parts of a [`Text`] separated by any of several
+/// delimiters
+///
+/// Created by [`Text::split_any`]
+#[derive(Clone, Debug)]
+pub struct SplitAny<'a> {
+    text: Text,
+    pos: usize,
+    delims: &'a [&'a str],
+    finished: bool,
+}
+
+impl<'a> SplitAny<'a> {
+    pub(crate) fn new(text: Text, delims: &'a [&'a str]) -> Self {
+        Self {
+            text,
+            pos: 0,
+            delims,
+            finished: false,
+        }
+    }
+
+    /// Finds the earliest delimiter at or after `start`, returning where it
+    /// starts and ends
+    fn find_delim(&self, start: usize) -> Option<(usize, usize)> {
+        self.text[start..].char_indices().find_map(|(i, _)| {
+            let rest = &self.text[start + i..];
+            self.delims
+                .iter()
+                .filter(|delim| !delim.is_empty() && rest.starts_with(**delim))
+                .map(|delim| delim.len())
+                .max()
+                .map(|len| (start + i, start + i + len))
+        })
+    }
+}
+
+impl Iterator for SplitAny<'_> {
+    type Item = Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let start = self.pos;
+        let end = match self.find_delim(start) {
+            Some((end, next)) => {
+                self.pos = next;
+                end
+            }
+            None => {
+                self.finished = true;
+                self.text.len()
+            }
+        };
+        // Safety: `start` and `end` are either the ends of the text, or next
+        // to a delimiter, which is valid UTF-8 on its own
+        Some(unsafe { self.text.subtext_unchecked(start, end) })
+    }
+}
+
+impl FusedIterator for SplitAny<'_> {}
+
+/// An iterator over the matches of a pattern in a [`Text`], along with the
+/// byte offset of each one
+///
+/// Created by [`Text::match_indices`]
+#[derive(Clone, Debug)]
+pub struct MatchIndices<P> {
+    text: Text,
+    pos: usize,
+    pat: P,
+    finished: bool,
+}
+
+impl<P: Pattern> MatchIndices<P> {
+    pub(crate) fn new(text: Text, pat: P) -> Self {
+        Self {
+            text,
+            pos: 0,
+            pat,
+            finished: false,
+        }
+    }
+}
+
+impl<P: Pattern> MatchIndices<P> {
+    /// The byte range of the next match, without slicing it out
+    fn next_range(&mut self) -> Option<(usize, usize)> {
+        if self.finished {
+            return None;
+        }
+        let (start, end) = match self.pat.next_match(&self.text[self.pos..]) {
+            Some((start, end)) => (self.pos + start, self.pos + end),
+            None => {
+                self.finished = true;
+                return None;
+            }
+        };
+        if start == end {
+            // an empty match can happen at every char boundary, so step over
+            // the next char to avoid matching at the same place forever
+            match self.text[end..].chars().next() {
+                Some(c) => self.pos = end + c.len_utf8(),
+                None => self.finished = true,
+            }
+        } else {
+            self.pos = end;
+        }
+        Some((start, end))
+    }
+}
+
+impl<P: Pattern> Iterator for MatchIndices<P> {
+    type Item = (usize, Text);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = self.next_range()?;
+        // Safety: `start` and `end` are the ends of a match found by `str`,
+        // so they're on char boundaries
+        Some((start, unsafe { self.text.subtext_unchecked(start, end) }))
+    }
+}
+
+impl<P: Pattern> FusedIterator for MatchIndices<P> {}
+
+/// An iterator over the matches of a pattern in a [`Text`]
+///
+/// Created by [`Text::matches`]
+#[derive(Clone, Debug)]
+pub struct Matches<P>(MatchIndices<P>);
+
+impl<P: Pattern> Matches<P> {
+    pub(crate) fn new(text: Text, pat: P) -> Self {
+        Self(MatchIndices::new(text, pat))
+    }
+}
+
+impl<P: Pattern> Iterator for Matches<P> {
+    type Item = Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, text)| text)
+    }
+}
+
+impl<P: Pattern> FusedIterator for Matches<P> {}
+
+/// An iterator over each char of a [`Text`] as its own `Text`, along with
+/// its byte offset
+///
+/// Created by [`Text::char_slices`]
+#[derive(Clone, Debug)]
+pub struct CharSlices {
+    text: Text,
+    start: usize,
+    end: usize,
+}
+
+impl CharSlices {
+    pub(crate) fn new(text: Text) -> Self {
+        let end = text.len();
+        Self {
+            text,
+            start: 0,
+            end,
+        }
+    }
+}
+
+impl Iterator for CharSlices {
+    type Item = (usize, Text);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.text[self.start..self.end].chars().next()?;
+        let start = self.start;
+        self.start += c.len_utf8();
+        // Safety: `start` and `self.start` are the ends of a single char
+        Some((start, unsafe {
+            self.text.subtext_unchecked(start, self.start)
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len.div_ceil(4), Some(len))
+    }
+}
+
+impl DoubleEndedIterator for CharSlices {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let c = self.text[self.start..self.end].chars().next_back()?;
+        let end = self.end;
+        self.end -= c.len_utf8();
+        // Safety: `self.end` and `end` are the ends of a single char
+        Some((self.end, unsafe {
+            self.text.subtext_unchecked(self.end, end)
+        }))
+    }
+}
+
+impl FusedIterator for CharSlices {}
+
+/// The shared implementation of [`SplitWhitespace`] and
+/// [`SplitAsciiWhitespace`]
+#[derive(Clone, Debug)]
+struct Tokens {
+    text: Text,
+    start: usize,
+    end: usize,
+    is_whitespace: fn(char) -> bool,
+}
+
+impl Tokens {
+    fn new(text: Text, is_whitespace: fn(char) -> bool) -> Self {
+        let end = text.len();
+        Self {
+            text,
+            start: 0,
+            end,
+            is_whitespace,
+        }
+    }
+}
+
+impl Iterator for Tokens {
+    type Item = Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let is_whitespace = self.is_whitespace;
+        let rest = self.text[self.start..self.end].trim_start_matches(is_whitespace);
+        if rest.is_empty() {
+            self.start = self.end;
+            return None;
+        }
+        let token = &rest[..rest.find(is_whitespace).unwrap_or(rest.len())];
+        let token = self.text.slice_ref(token);
+        self.start = token.as_ptr() as usize - self.text.as_ptr() as usize + token.len();
+        Some(token)
+    }
+}
+
+impl DoubleEndedIterator for Tokens {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let is_whitespace = self.is_whitespace;
+        let rest = self.text[self.start..self.end].trim_end_matches(is_whitespace);
+        if rest.is_empty() {
+            self.end = self.start;
+            return None;
+        }
+        let token_start = rest.trim_end_matches(|c| !is_whitespace(c)).len();
+        let token = self.text.slice_ref(&rest[token_start..]);
+        self.end = token.as_ptr() as usize - self.text.as_ptr() as usize;
+        Some(token)
+    }
+}
+
+/// An iterator over the whitespace-separated tokens of a [`Text`]
+///
+/// Created by [`Text::split_whitespace`]
+#[derive(Clone, Debug)]
+pub struct SplitWhitespace(Tokens);
+
+impl SplitWhitespace {
+    pub(crate) fn new(text: Text) -> Self {
+        Self(Tokens::new(text, char::is_whitespace))
+    }
+}
+
+impl Iterator for SplitWhitespace {
+    type Item = Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl DoubleEndedIterator for SplitWhitespace {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl FusedIterator for SplitWhitespace {}
+
+/// An iterator over the ASCII-whitespace-separated tokens of a [`Text`]
+///
+/// Created by [`Text::split_ascii_whitespace`]
+#[derive(Clone, Debug)]
+pub struct SplitAsciiWhitespace(Tokens);
+
+impl SplitAsciiWhitespace {
+    pub(crate) fn new(text: Text) -> Self {
+        Self(Tokens::new(text, |c| c.is_ascii_whitespace()))
+    }
+}
+
+impl Iterator for SplitAsciiWhitespace {
+    type Item = Text;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl DoubleEndedIterator for SplitAsciiWhitespace {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl FusedIterator for SplitAsciiWhitespace {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_fields() {
+        let text = Text::from(r#"plain,"with, comma","dou""bled""",,"""",tail"#);
+        let fields: Vec<Text> = text.quoted_fields(',', '"').collect();
+        assert_eq!(
+            fields,
+            ["plain", "with, comma", r#"dou"bled""#, "", r#"""#, "tail"]
+        );
+        // unescaped fields aren't copied
+        assert_eq!(fields[0].as_ptr(), text.as_ptr());
+        assert_eq!(fields[1].as_ptr(), text[7..].as_ptr());
+
+        let fields: Vec<Text> = Text::from("").quoted_fields(',', '"').collect();
+        assert_eq!(fields, [""]);
+        let fields: Vec<Text> = Text::from("a,").quoted_fields(',', '"').collect();
+        assert_eq!(fields, ["a", ""]);
+
+        // text after the closing quote, and unclosed quotes
+        let fields: Vec<Text> = Text::from(r#""ab"cd;"unclosed;"#)
+            .quoted_fields(';', '"')
+            .collect();
+        assert_eq!(fields, ["abcd", "unclosed;"]);
+
+        // multi-byte delimiters and quotes
+        let fields: Vec<Text> = Text::from("«a→b«→«c««d«").quoted_fields('→', '«').collect();
+        assert_eq!(fields, ["a→b", "c«d"]);
+    }
+
+    #[test]
+    fn lines_with_endings() {
+        let text = Text::from("first\r\nsecond\n\r\nfourth\rstill\nlast");
+        let lines: Vec<(Text, Text)> = text.lines_with_endings().collect();
+        assert_eq!(
+            lines,
+            [
+                ("first".into(), "\r\n".into()),
+                ("second".into(), "\n".into()),
+                ("".into(), "\r\n".into()),
+                ("fourth\rstill".into(), "\n".into()),
+                ("last".into(), "".into()),
+            ]
+        );
+        // the lines match `str::lines`
+        assert!(lines.iter().map(|(line, _)| line).eq(str::lines(&text)));
+        // round-trips
+        let joined: String = lines.iter().map(|(l, e)| format!("{}{}", l, e)).collect();
+        assert_eq!(text, joined);
+
+        let lines: Vec<(Text, Text)> = Text::from("a\n").lines_with_endings().collect();
+        assert_eq!(lines, [("a".into(), "\n".into())]);
+        let lines: Vec<(Text, Text)> = Text::from("\n\n").lines_with_endings().collect();
+        assert_eq!(lines, [("".into(), "\n".into()), ("".into(), "\n".into())]);
+        assert_eq!(Text::new().lines_with_endings().count(), 0);
+    }
+
+    #[test]
+    fn split_any() {
+        let newlines = ["\n", "\r", "\r\n"];
+        let text = Text::from("unix\nmac\rwindows\r\n\n\rend");
+        let parts: Vec<Text> = text.split_any(&newlines).collect();
+        // `\r\n` wins over `\r` because it's longer
+        assert_eq!(parts, ["unix", "mac", "windows", "", "", "end"]);
+        assert_eq!(parts[1].as_ptr(), text[5..].as_ptr());
+
+        let parts: Vec<Text> = Text::from("a\r\n").split_any(&newlines).collect();
+        assert_eq!(parts, ["a", ""]);
+        let parts: Vec<Text> = Text::from("").split_any(&newlines).collect();
+        assert_eq!(parts, [""]);
+
+        // empty delimiters never match
+        let parts: Vec<Text> = Text::from("a→b").split_any(&["", "→"]).collect();
+        assert_eq!(parts, ["a", "b"]);
+        let parts: Vec<Text> = Text::from("abc").split_any(&[]).collect();
+        assert_eq!(parts, ["abc"]);
+    }
+
+    #[test]
+    fn lines_with_endings_back() {
+        let texts = [
+            "first\r\nsecond\n\r\nfourth\rstill\nlast",
+            "a\n\nb\r\n",
+            "\r\n\n\r",
+            "\n",
+            "",
+        ];
+        for text in texts {
+            let text = Text::from(text);
+            let forward: Vec<(Text, Text)> = text.lines_with_endings().collect();
+            let mut backward: Vec<(Text, Text)> = text.lines_with_endings().rev().collect();
+            backward.reverse();
+            assert_eq!(forward, backward);
+        }
+
+        let text = Text::from("one\ntwo\r\nthree\nfour");
+        let mut lines = text.lines_with_endings();
+        assert_eq!(lines.next_back(), Some(("four".into(), "".into())));
+        assert_eq!(lines.next(), Some(("one".into(), "\n".into())));
+        assert_eq!(lines.next_back(), Some(("three".into(), "\n".into())));
+        assert_eq!(lines.next(), Some(("two".into(), "\r\n".into())));
+        assert_eq!(lines.next_back(), None);
+        assert_eq!(lines.next(), None);
+        // fused
+        assert_eq!(lines.next_back(), None);
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn lines() {
+        let texts = [
+            "one\r\ntwo\r\nthree\r\n",
+            "no trailing newline\nhere",
+            "\n\n\n",
+            "\r\n",
+            "bare\rreturn\r",
+            "",
+        ];
+        for s in texts {
+            let text = Text::from(s);
+            assert!(text.lines().eq(s.lines()), "{:?}", s);
+            assert!(text.lines().rev().eq(s.lines().rev()), "{:?}", s);
+        }
+
+        let text = Text::from("a\r\nb\n\nc");
+        let lines: Vec<Text> = text.lines().collect();
+        assert_eq!(lines, ["a", "b", "", "c"]);
+        assert_eq!(lines[1].as_ptr(), text[3..].as_ptr());
+
+        let lines: Vec<Text> = Text::from("\n\n").lines().collect();
+        assert_eq!(lines, ["", ""]);
+    }
+
+    #[test]
+    fn split() {
+        let texts = [",a,", "a,b,,c", "", ",", "no delimiter", "é,ü,ñ"];
+        for s in texts {
+            let text = Text::from(s);
+            assert!(text.split(',').eq(s.split(',')), "{:?}", s);
+            assert!(text.rsplit(',').eq(s.rsplit(',')), "{:?}", s);
+            for n in 0..5 {
+                assert!(text.splitn(n, ',').eq(s.splitn(n, ',')), "{:?}", s);
+                assert!(text.rsplitn(n, ',').eq(s.rsplitn(n, ',')), "{:?}", s);
+            }
+        }
+
+        let text = Text::from("a→bc→");
+        let parts: Vec<Text> = text.split('→').collect();
+        assert_eq!(parts, ["a", "bc", ""]);
+        assert_eq!(parts[1].as_ptr(), text[4..].as_ptr());
+
+        let parts: Vec<Text> = text.splitn(2, '→').collect();
+        assert_eq!(parts, ["a", "bc→"]);
+        assert_eq!(parts[1].as_ptr(), text[4..].as_ptr());
+
+        let parts: Vec<Text> = text.rsplit('→').collect();
+        assert_eq!(parts, ["", "bc", "a"]);
+        assert_eq!(parts[1].as_ptr(), text[4..].as_ptr());
+
+        // string and closure delimiters, like `str::split`
+        let text = Text::from("a, b,, c");
+        let parts: Vec<Text> = text.split(", ").collect();
+        assert_eq!(parts, ["a", "b,", "c"]);
+        assert_eq!(parts[2].as_ptr(), text[7..].as_ptr());
+        assert!(text
+            .split(char::is_whitespace)
+            .eq(str::split(&text, char::is_whitespace)));
+        assert!(text.split(|c: char| c == ',').eq(str::split(&text, ',')));
+        assert!(text.split(['a', ' ']).eq(str::split(&text, ['a', ' '])));
+        let parts: Vec<Text> = text.splitn(2, ", ").collect();
+        assert_eq!(parts, ["a", "b,, c"]);
+        let parts: Vec<Text> = text.rsplit(", ").collect();
+        assert_eq!(parts, ["c", "b,", "a"]);
+        assert_eq!(parts[1].as_ptr(), text[3..].as_ptr());
+        assert!(text
+            .rsplit(char::is_whitespace)
+            .eq(str::rsplit(&text, char::is_whitespace)));
+        assert!(text.rsplit(|c: char| c == ',').eq(str::rsplit(&text, ',')));
+        let parts: Vec<Text> = text.rsplitn(2, ", ").collect();
+        assert_eq!(parts, ["c", "a, b,"]);
+        let aaa = Text::from("aaa");
+        assert!(aaa.rsplit("aa").eq(str::rsplit(&aaa, "aa")));
+        for s in ["", "ab", "é🦀"] {
+            let text = Text::from(s);
+            assert!(text.split("").eq(s.split("")), "{:?}", s);
+            assert!(text.splitn(2, "").eq(s.splitn(2, "")), "{:?}", s);
+            assert!(text.rsplit("").eq(s.rsplit("")), "{:?}", s);
+            for n in 0..4 {
+                assert!(text.rsplitn(n, "").eq(s.rsplitn(n, "")), "{:?}", s);
+            }
+        }
+
+        let text = Text::from("a:b:c:d");
+        let parts: Vec<Text> = text.splitn(2, ':').collect();
+        assert_eq!(parts, ["a", "b:c:d"]);
+        let parts: Vec<Text> = text.rsplitn(2, ':').collect();
+        assert_eq!(parts, ["d", "a:b:c"]);
+        assert_eq!(parts[1].as_ptr(), text.as_ptr());
+    }
+
+    #[test]
+    fn split_whitespace() {
+        let text = Text::from(" \t lead  mid\u{3000}\u{a0}dle\n\ntrail \r\n");
+        let tokens: Vec<Text> = text.split_whitespace().collect();
+        let expected: Vec<&str> = str::split_whitespace(&text).collect();
+        assert_eq!(tokens, expected);
+        assert_eq!(tokens, ["lead", "mid", "dle", "trail"]);
+        for token in &tokens {
+            assert!(text.as_bytes().as_ptr_range().contains(&token.as_ptr()));
+        }
+        let back: Vec<Text> = text.split_whitespace().rev().collect();
+        assert_eq!(back, ["trail", "dle", "mid", "lead"]);
+
+        let mut both = text.split_whitespace();
+        assert_eq!(both.next().unwrap(), "lead");
+        assert_eq!(both.next_back().unwrap(), "trail");
+        assert_eq!(both.next_back().unwrap(), "dle");
+        assert_eq!(both.next().unwrap(), "mid");
+        assert!(both.next().is_none());
+        assert!(both.next_back().is_none());
+
+        let blank = Text::from(" \t\u{3000}\n ");
+        assert!(blank.split_whitespace().next().is_none());
+        assert!(Text::new().split_whitespace().next_back().is_none());
+    }
+
+    #[test]
+    fn split_ascii_whitespace() {
+        let text = Text::from("  a\u{3000}b \t c\u{a0} \n");
+        let tokens: Vec<Text> = text.split_ascii_whitespace().collect();
+        let expected: Vec<&str> = str::split_ascii_whitespace(&text).collect();
+        assert_eq!(tokens, expected);
+        assert_eq!(tokens, ["a\u{3000}b", "c\u{a0}"]);
+        let back: Vec<Text> = text.split_ascii_whitespace().rev().collect();
+        assert_eq!(back, ["c\u{a0}", "a\u{3000}b"]);
+
+        assert!(Text::from(" \t\n\r\x0c ")
+            .split_ascii_whitespace()
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn match_indices() {
+        let check = |text: &str, pat: &str| {
+            let text = Text::copy_from(text);
+            let found: Vec<(usize, Text)> = text.match_indices(pat).collect();
+            let expected: Vec<(usize, &str)> = str::match_indices(&text, pat).collect();
+            assert_eq!(found.len(), expected.len());
+            for ((i, m), (j, n)) in found.iter().zip(&expected) {
+                assert_eq!((i, &**m), (j, *n));
+                assert_eq!(m.as_ptr(), text[*i..].as_ptr());
+            }
+            let matches: Vec<Text> = text.matches(pat).collect();
+            assert_eq!(matches, str::matches(&text, pat).collect::<Vec<_>>());
+            found.into_iter().map(|(i, _)| i).collect::<Vec<_>>()
+        };
+
+        assert!(check("nothing here", "xyz").is_empty());
+        assert_eq!(check("abab", "ab"), [0, 2]);
+        assert_eq!(check("aaaaa", "aa"), [0, 2]);
+        assert_eq!(check("a🦀b🦀🦀", "🦀"), [1, 6, 10]);
+        assert_eq!(check("über über", "über"), [0, 6]);
+        assert_eq!(check("é🦀", ""), [0, 2, 6]);
+        assert_eq!(check("", ""), [0]);
+
+        // other patterns, like `str::match_indices`
+        let text = Text::from("a1é22🦀3");
+        let found: Vec<(usize, Text)> = text.match_indices('2').collect();
+        assert_eq!(found, [(4, "2".into()), (5, "2".into())]);
+        assert_eq!(found[0].1.as_ptr(), text[4..].as_ptr());
+        let found: Vec<usize> = text.match_indices('🦀').map(|(i, _)| i).collect();
+        assert_eq!(found, [6]);
+        let digits: Vec<Text> = text.matches(char::is_numeric).collect();
+        assert_eq!(digits, ["1", "2", "2", "3"]);
+        let set: &[char] = &['é', '🦀'];
+        let found: Vec<(usize, Text)> = text.match_indices(set).collect();
+        assert_eq!(found, [(2, "é".into()), (6, "🦀".into())]);
+        assert_eq!(text.matches(|c: char| !c.is_ascii()).count(), 2);
+        assert_eq!(text.matches('x').next(), None);
+    }
+
+    #[test]
+    fn char_slices() {
+        let text = Text::from("aé🦀z");
+        let slices: Vec<(usize, Text)> = text.char_slices().collect();
+        let expected: Vec<(usize, char)> = text.char_indices().collect();
+        assert_eq!(slices.len(), expected.len());
+        for ((i, slice), (j, c)) in slices.iter().zip(&expected) {
+            assert_eq!(i, j);
+            assert_eq!(slice.chars().collect::<Vec<_>>(), [*c]);
+            assert_eq!(slice.as_ptr(), text[*i..].as_ptr());
+        }
+        assert_eq!(slices[2], (3, "🦀".into()));
+
+        let back: Vec<(usize, Text)> = text.char_slices().rev().collect();
+        let mut forward = slices;
+        forward.reverse();
+        assert_eq!(back, forward);
+
+        let mut both = text.char_slices();
+        assert_eq!(both.next().unwrap().0, 0);
+        assert_eq!(both.next_back().unwrap().0, 7);
+        assert_eq!(both.next_back().unwrap(), (3, "🦀".into()));
+        assert_eq!(both.next().unwrap(), (1, "é".into()));
+        assert!(both.next().is_none());
+        assert!(Text::new().char_slices().next().is_none());
+    }
+}
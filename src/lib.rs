@@ -12,12 +12,82 @@
 //! assert_eq!(hello, "Hello");
 //!
 //! ```
+//!
+//! # `no_std`
+//!
+//! This crate only needs `core` and `alloc`, so it can be used without the
+//! standard library by turning off the default `std` feature. Without it,
+//! the error types don't implement `std::error::Error`, and the
+//! `unicode-case` feature isn't available.
+//!
+//! ```
+//! #![no_std]
+//! extern crate alloc;
+//! # // doctests are binaries, which need std's allocator and panic handler
+//! # extern crate std;
+//!
+//! use alloc::string::String;
+//! use bytes_text::{Text, TextMut};
+//!
+//! # fn main() {
+//! let mut text = TextMut::new();
+//! text.push_str("no std");
+//! text.push_str(" needed");
+//! let text: Text = text.freeze();
+//! assert_eq!(text.split_once(' ').unwrap().0, "no");
+//! assert_eq!(text.into_string(), String::from("no std needed"));
+//! # }
+//! ```
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
 #![warn(rustdoc::missing_doc_code_examples)] // this doesn't seem to do anything
 
+extern crate alloc;
+
+mod arena;
+mod ascii;
+mod counted;
+mod decoder;
+mod error;
+mod iter;
+mod pattern;
 mod text;
 mod text_mut;
 
+pub use arena::TextArena;
+pub use ascii::AsciiText;
+pub use counted::CountedText;
+pub use decoder::Utf8Decoder;
+pub use error::{FromUtf8Error, Utf8Diagnostic};
+pub use iter::{
+    CharSlices, Lines, LinesWithEndings, MatchIndices, Matches, QuotedFields, RSplit, RSplitN,
+    Split, SplitAny, SplitAsciiWhitespace, SplitN, SplitWhitespace,
+};
+pub use pattern::{CharPattern, Pattern};
 pub use text::Text;
-pub use text_mut::TextMut;
+pub use text_mut::{LineEnding, TextMut};
+
+/// Creates a [`Text`] using interpolation of runtime expressions
+///
+/// This works like [`format!`], but writes directly into a [`TextMut`]
+/// and freezes it, rather than producing a `String`.
+///
+/// # Example
+///
+/// ```
+/// use bytes_text::{text, Text};
+///
+/// let (a, b) = (4, "two");
+/// let t: Text = text!("{}-{}", a, b);
+/// assert_eq!(t, "4-two");
+/// ```
+#[macro_export]
+macro_rules! text {
+    ($($arg:tt)*) => {{
+        let mut text = $crate::TextMut::new();
+        // writing to a `TextMut` can't fail
+        let _ = ::core::fmt::Write::write_fmt(&mut text, ::core::format_args!($($arg)*));
+        text.freeze()
+    }};
+}
@@ -0,0 +1,144 @@
+use alloc::string::String;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Something that can be searched for in a [`Text`](crate::Text)
+///
+/// This is a stand-in for the unstable `std::str::pattern::Pattern`, used by
+/// [`Text::find`](crate::Text::find), [`Text::rfind`](crate::Text::rfind),
+/// [`Text::strip_prefix`](crate::Text::strip_prefix),
+/// [`Text::strip_suffix`](crate::Text::strip_suffix) and
+/// [`Text::matches`](crate::Text::matches), among others.
+/// It's implemented for the same types as `str`'s patterns: `char`, string
+/// slices, slices or arrays of `char`s (which match any of their chars), and
+/// `FnMut(char) -> bool` closures. It can't be implemented outside this
+/// crate.
+pub trait Pattern: private::Sealed {
+    /// The byte offset of the first match in `haystack`
+    #[doc(hidden)]
+    fn find_in(self, haystack: &str) -> Option<usize>;
+
+    /// The byte offset of the last match in `haystack`
+    #[doc(hidden)]
+    fn rfind_in(self, haystack: &str) -> Option<usize>;
+
+    /// `haystack` without a leading match
+    #[doc(hidden)]
+    fn strip_prefix_of(self, haystack: &str) -> Option<&str>;
+
+    /// `haystack` without a trailing match
+    #[doc(hidden)]
+    fn strip_suffix_of(self, haystack: &str) -> Option<&str>;
+
+    /// `haystack` without any leading matches
+    #[doc(hidden)]
+    fn trim_start_matches_of(self, haystack: &str) -> &str;
+
+    /// `haystack` without any trailing matches
+    #[doc(hidden)]
+    fn trim_end_matches_of(self, haystack: &str) -> &str;
+
+    /// The byte range of the first match in `haystack`, leaving the pattern
+    /// in place to search again
+    #[doc(hidden)]
+    fn next_match(&mut self, haystack: &str) -> Option<(usize, usize)>;
+
+    /// The byte range of the last match in `haystack`, leaving the pattern
+    /// in place to search again
+    #[doc(hidden)]
+    fn next_match_back(&mut self, haystack: &str) -> Option<(usize, usize)>;
+}
+
+/// Implements `Pattern` by handing `$std` to the matching `str` method, where
+/// `$p` is a `&mut` to the pattern
+macro_rules! impl_pattern {
+    ([$($generics:tt)*] $ty:ty, |$p:ident| $std:expr) => {
+        impl<$($generics)*> private::Sealed for $ty {}
+
+        impl<$($generics)*> Pattern for $ty {
+            fn find_in(mut self, haystack: &str) -> Option<usize> {
+                let $p = &mut self;
+                haystack.find($std)
+            }
+
+            fn rfind_in(mut self, haystack: &str) -> Option<usize> {
+                let $p = &mut self;
+                haystack.rfind($std)
+            }
+
+            fn strip_prefix_of(mut self, haystack: &str) -> Option<&str> {
+                let $p = &mut self;
+                haystack.strip_prefix($std)
+            }
+
+            fn strip_suffix_of(mut self, haystack: &str) -> Option<&str> {
+                let $p = &mut self;
+                haystack.strip_suffix($std)
+            }
+
+            fn trim_start_matches_of(mut self, haystack: &str) -> &str {
+                let $p = &mut self;
+                haystack.trim_start_matches($std)
+            }
+
+            fn trim_end_matches_of(mut self, haystack: &str) -> &str {
+                let $p = &mut self;
+                haystack.trim_end_matches($std)
+            }
+
+            fn next_match(&mut self, haystack: &str) -> Option<(usize, usize)> {
+                let $p = self;
+                let mut matches = haystack.match_indices($std);
+                matches.next().map(|(i, m)| (i, i + m.len()))
+            }
+
+            fn next_match_back(&mut self, haystack: &str) -> Option<(usize, usize)> {
+                let $p = self;
+                let mut matches = haystack.rmatch_indices($std);
+                matches.next().map(|(i, m)| (i, i + m.len()))
+            }
+        }
+    };
+}
+
+impl_pattern!([] char, |p| *p);
+impl_pattern!([] & str, |p| *p);
+impl_pattern!([] && str, |p| *p);
+impl_pattern!([] & String, |p| p.as_str());
+impl_pattern!([] & [char], |p| *p);
+impl_pattern!([const N: usize] [char; N], |p| *p);
+impl_pattern!([const N: usize] &[char; N], |p| *p);
+impl_pattern!([F: FnMut(char) -> bool] F, |p| &mut *p);
+
+/// A [`Pattern`] that matches single chars
+///
+/// These are the patterns that [`Text::trim_matches`](crate::Text::trim_matches)
+/// takes: a `char`, a slice or array of `char`s to match any of, or an
+/// `FnMut(char) -> bool` closure. Unlike its one-sided versions, it can't
+/// take a string, just like [`str::trim_matches`]. It can't be implemented
+/// outside this crate.
+pub trait CharPattern: Pattern {
+    /// `haystack` without any leading or trailing matches
+    #[doc(hidden)]
+    fn trim_matches_of(self, haystack: &str) -> &str;
+}
+
+/// Implements `CharPattern` like `impl_pattern`
+macro_rules! impl_char_pattern {
+    ([$($generics:tt)*] $ty:ty, |$p:ident| $std:expr) => {
+        impl<$($generics)*> CharPattern for $ty {
+            fn trim_matches_of(mut self, haystack: &str) -> &str {
+                let $p = &mut self;
+                haystack.trim_matches($std)
+            }
+        }
+    };
+}
+
+impl_char_pattern!([] char, |p| *p);
+impl_char_pattern!([] & [char], |p| *p);
+impl_char_pattern!([const N: usize] [char; N], |p| *p);
+impl_char_pattern!([const N: usize] &[char; N], |p| *p);
+impl_char_pattern!([F: FnMut(char) -> bool] F, |p| &mut *p);
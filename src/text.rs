@@ -1,15 +1,28 @@
-use std::{
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    rc::Rc,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+use core::{
     borrow::Borrow,
     convert::TryFrom,
     fmt::{Debug, Display},
     hash::Hash,
-    ops::{Deref, RangeBounds},
-    str::Utf8Error,
+    iter::FromIterator,
+    ops::{Deref, Range, RangeBounds},
+    str::{EncodeUtf16, Utf8Error},
 };
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 
-use crate::TextMut;
+use crate::{
+    AsciiText, CharPattern, CharSlices, FromUtf8Error, Lines, LinesWithEndings, MatchIndices,
+    Matches, Pattern, QuotedFields, RSplit, RSplitN, Split, SplitAny, SplitAsciiWhitespace, SplitN,
+    SplitWhitespace, TextMut, Utf8Diagnostic,
+};
 
 /// Immutable, reference counted, UTF-8 text
 ///
@@ -32,15 +45,20 @@ pub struct Text(Bytes);
 impl Text {
     /// Creates a new, empty, text buffer.
     ///
+    /// This doesn't allocate, and can be used in constant expressions.
+    ///
     /// # Example
     ///
     /// ```
     /// # use bytes_text::Text;
     /// let mut text = Text::new();
     /// assert!(text.is_empty());
+    ///
+    /// const EMPTY: Text = Text::new();
+    /// assert_eq!(EMPTY, text);
     /// ```
-    pub fn new() -> Self {
-        Self(Bytes::new())
+    pub const fn new() -> Self {
+        Self::from_static("")
     }
 
     /// Converts `Bytes` to `Text`.
@@ -58,10 +76,62 @@ impl Text {
     /// let text = Text::from_utf8(buf).unwrap();
     /// assert_eq!(text, "i'm in a buffer!");
     /// ```
-    pub fn from_utf8(b: Bytes) -> Result<Self, Utf8Error> {
+    ///
+    /// If `b` isn't valid UTF-8, the returned error holds on to it so it can be
+    /// recovered with [`FromUtf8Error::into_bytes`].
+    pub fn from_utf8(b: Bytes) -> Result<Self, FromUtf8Error> {
         // run utf-8 validation
-        let _ = std::str::from_utf8(b.as_ref())?;
-        Ok(Self(b))
+        match core::str::from_utf8(b.as_ref()) {
+            Ok(_) => Ok(Self(b)),
+            Err(e) => Err(FromUtf8Error::new(b, e)),
+        }
+    }
+
+    /// Converts `Bytes` to `Text`, with extra context about where the input
+    /// went wrong if it isn't valid UTF-8
+    ///
+    /// This is the same as [`Text::from_utf8`], but the error includes the
+    /// invalid bytes and a hex snippet of the bytes around them, to make
+    /// debugging bad input easier.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// # use bytes::Bytes;
+    /// let buf = Bytes::from_static(b"id=\xc3\x28");
+    /// let err = Text::from_utf8_diagnostic(buf).unwrap_err();
+    /// assert_eq!(err.valid_up_to(), 3);
+    /// assert_eq!(err.to_string(), "invalid utf-8 sequence at byte 3: 69 64 3d [c3] 28");
+    /// ```
+    pub fn from_utf8_diagnostic(b: Bytes) -> Result<Self, Utf8Diagnostic> {
+        match core::str::from_utf8(b.as_ref()) {
+            Ok(_) => Ok(Self(b)),
+            Err(e) => Err(Utf8Diagnostic::new(&b, e)),
+        }
+    }
+
+    /// Converts `Bytes` to `Text`, replacing any invalid UTF-8 sequences
+    /// with `U+FFFD REPLACEMENT CHARACTER`
+    ///
+    /// If `b` is already valid UTF-8, it is wrapped without copying.
+    /// Otherwise, a new buffer is allocated to hold the replaced text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// # use bytes::Bytes;
+    /// let buf = Bytes::from_static(b"Hello \xF0\x90\x80World");
+    ///
+    /// let text = Text::from_utf8_lossy(buf);
+    /// assert_eq!(text, "Hello �World");
+    /// ```
+    pub fn from_utf8_lossy(b: Bytes) -> Self {
+        match String::from_utf8_lossy(b.as_ref()) {
+            Cow::Borrowed(_) => Self(b),
+            Cow::Owned(s) => Self::from(s),
+        }
     }
 
     /// Converts `Bytes` to `Text` without verifying that it's valid UTF-8
@@ -175,6 +245,102 @@ impl Text {
         self.0
     }
 
+    /// Copies the contents into a new, mutable buffer of raw bytes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// # use bytes::{BytesMut, BufMut};
+    /// let text = Text::from("Woah");
+    /// let mut bytes: BytesMut = text.to_bytes_mut();
+    /// bytes.put_u8(b'!');
+    /// assert_eq!(&bytes, &b"Woah!"[..]);
+    /// assert_eq!(text, "Woah");
+    /// ```
+    pub fn to_bytes_mut(&self) -> BytesMut {
+        BytesMut::from(self.0.as_ref())
+    }
+
+    /// Borrows the bytes of this text as a `Cow`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// use std::borrow::Cow;
+    ///
+    /// fn checksum(bytes: Cow<'_, [u8]>) -> u32 {
+    ///     bytes.iter().map(|&b| u32::from(b)).sum()
+    /// }
+    ///
+    /// let text = Text::from("abc");
+    /// assert_eq!(checksum(text.as_cow_bytes()), 97 + 98 + 99);
+    /// ```
+    pub fn as_cow_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.0)
+    }
+
+    /// Converts this text into an owned `Cow` of its bytes
+    ///
+    /// Like [`Text::into_string`], this only copies if this isn't the only
+    /// handle to the underlying buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// use std::borrow::Cow;
+    ///
+    /// let bytes: Cow<'static, [u8]> = Text::from("abc").into_cow_bytes();
+    /// assert_eq!(bytes, &b"abc"[..]);
+    /// ```
+    pub fn into_cow_bytes(self) -> Cow<'static, [u8]> {
+        Cow::Owned(Vec::from(self.0))
+    }
+
+    /// Convert into a `String`
+    ///
+    /// If this is the only handle to the underlying buffer, the buffer is
+    /// reused and nothing is copied. Otherwise (e.g. if the text has been
+    /// cloned, or was created with [`Text::from_static`]), the contents are
+    /// copied into a new `String`.
+    ///
+    /// `to_string`, from the blanket [`ToString`] impl, always copies. To
+    /// make that explicit, use [`Text::as_string`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from(String::from("Woah"));
+    /// let string: String = text.into_string();
+    /// assert_eq!(string, "Woah");
+    /// ```
+    pub fn into_string(self) -> String {
+        let vec = Vec::from(self.0);
+        // Safety: the bytes came from `self`, which is valid UTF-8
+        unsafe { String::from_utf8_unchecked(vec) }
+    }
+
+    /// Copies the contents into a new `String`
+    ///
+    /// This leaves `self` untouched. To reuse the buffer when possible, use
+    /// [`Text::into_string`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("Woah");
+    /// let string: String = text.as_string();
+    /// assert_eq!(string, "Woah");
+    /// assert_eq!(text, "Woah");
+    /// ```
+    pub fn as_string(&self) -> String {
+        self.as_str().to_owned()
+    }
+
     /// Get a sub-body of text
     ///
     /// # Example
@@ -189,25 +355,245 @@ impl Text {
     /// assert_eq!(middle, "some");
     /// ```
     pub fn get(&self, r: impl RangeBounds<usize>) -> Option<Text> {
-        let start = match r.start_bound() {
-            std::ops::Bound::Included(&i) => i,
-            std::ops::Bound::Excluded(&i) => i.checked_add(1)?,
-            std::ops::Bound::Unbounded => 0,
-        };
-        let end = match r.end_bound() {
-            std::ops::Bound::Included(&i) => i.checked_add(1)?,
-            std::ops::Bound::Excluded(&i) => i,
-            std::ops::Bound::Unbounded => self.len(),
-        };
-        // str::is_char_boundary returns false if the index is out of bounds,
-        // so there's no need to check for it here
+        let (start, end) = char_range(self, r)?;
+        Some(Self(self.0.slice(start..end)))
+    }
+
+    /// Gets the text from byte `start` up to byte `end`
+    ///
+    /// This is the same as [`Text::get`] with `start..end`, but isn't generic
+    /// over the range type.
+    ///
+    /// Returns `None` if either end isn't a char boundary, or `start > end`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("añb🦀");
+    /// assert_eq!(text.subtext(1, 4).unwrap(), "ñb");
+    /// assert!(text.subtext(2, 4).is_none());
+    /// assert!(text.subtext(4, 1).is_none());
+    /// ```
+    pub fn subtext(&self, start: usize, end: usize) -> Option<Text> {
+        soft_assert::soft_assert!(start <= end);
         soft_assert::soft_assert!(self.is_char_boundary(start) && self.is_char_boundary(end));
         Some(Self(self.0.slice(start..end)))
     }
 
-    /// Splits the text into two halves
+    /// Gets the text from byte `start` up to byte `end`, without checking
+    /// that they're valid
+    ///
+    /// # Safety
+    ///
+    /// `start` and `end` must both be char boundaries no greater than the
+    /// length of the text, with `start <= end`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("key: value");
+    /// let colon = text.find(':').unwrap();
+    /// // Safety: `:` is one byte long, so both ends are char boundaries
+    /// let value = unsafe { text.subtext_unchecked(colon + 2, text.len()) };
+    /// assert_eq!(value, "value");
+    /// ```
+    pub unsafe fn subtext_unchecked(&self, start: usize, end: usize) -> Text {
+        Self(self.0.slice(start..end))
+    }
+
+    /// Gets the part of this text that `subslice` borrows, without copying
+    ///
+    /// This turns the `&str`s returned by the methods on `str` (like
+    /// [`str::trim_matches`]) back into `Text`s that share this text's
+    /// buffer. An empty `subslice` always gives an empty text.
+    ///
+    /// # Panics
+    ///
+    /// Like [`Bytes::slice_ref`], this panics if `subslice` doesn't point
+    /// inside this text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("--padded--");
+    /// let trimmed = text.slice_ref(str::trim_matches(&text, '-'));
+    /// assert_eq!(trimmed, "padded");
+    /// assert_eq!(trimmed.as_ptr(), text[2..].as_ptr());
+    /// ```
+    pub fn slice_ref(&self, subslice: &str) -> Text {
+        Self(self.0.slice_ref(subslice.as_bytes()))
+    }
+
+    /// Returns this text with leading and trailing whitespace removed
+    ///
+    /// Whitespace is defined like [`str::trim`], but the returned text
+    /// shares this text's buffer instead of borrowing from it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("\t hello world \n");
+    /// assert_eq!(text.trim(), "hello world");
+    /// ```
+    pub fn trim(&self) -> Text {
+        self.slice_ref(str::trim(self))
+    }
+
+    /// Returns this text with leading whitespace removed
+    ///
+    /// Whitespace is defined like [`str::trim_start`], but the returned text
+    /// shares this text's buffer instead of borrowing from it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("\t hello world \n");
+    /// assert_eq!(text.trim_start(), "hello world \n");
+    /// ```
+    pub fn trim_start(&self) -> Text {
+        self.slice_ref(str::trim_start(self))
+    }
+
+    /// Returns this text with trailing whitespace removed
     ///
-    /// Returns `Err(self)` if the index is not a valid char boundary
+    /// Whitespace is defined like [`str::trim_end`], but the returned text
+    /// shares this text's buffer instead of borrowing from it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("\t hello world \n");
+    /// assert_eq!(text.trim_end(), "\t hello world");
+    /// ```
+    pub fn trim_end(&self) -> Text {
+        self.slice_ref(str::trim_end(self))
+    }
+
+    /// Removes all leading and trailing chars matching `pat`, without copying
+    ///
+    /// `pat` can be a `char`, a slice or array of `char`s to match any of, or
+    /// a closure, as with [`str::trim_matches`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("\"quoted\"");
+    /// assert_eq!(text.trim_matches('"'), "quoted");
+    /// let id = Text::from("123abc45");
+    /// assert_eq!(id.trim_matches(char::is_numeric), "abc");
+    /// ```
+    pub fn trim_matches<P: CharPattern>(&self, pat: P) -> Text {
+        self.slice_ref(pat.trim_matches_of(self))
+    }
+
+    /// Removes all leading matches of `pat`, without copying
+    ///
+    /// `pat` can be any [`Pattern`], as with [`str::trim_start_matches`], so
+    /// repeated strings are removed too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("000120");
+    /// assert_eq!(text.trim_start_matches('0'), "120");
+    /// let item = Text::from("- * item");
+    /// assert_eq!(item.trim_start_matches(['-', '*', ' ']), "item");
+    /// let comment = Text::from("<!--<!--comment-->");
+    /// assert_eq!(comment.trim_start_matches("<!--"), "comment-->");
+    /// ```
+    pub fn trim_start_matches<P: Pattern>(&self, pat: P) -> Text {
+        self.slice_ref(pat.trim_start_matches_of(self))
+    }
+
+    /// Removes all trailing matches of `pat`, without copying
+    ///
+    /// `pat` can be any [`Pattern`], as with [`str::trim_end_matches`], so
+    /// repeated strings are removed too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("1.500");
+    /// assert_eq!(text.trim_end_matches('0'), "1.5");
+    /// let whole = Text::from("2.000");
+    /// assert_eq!(whole.trim_end_matches(|c: char| c == '0' || c == '.'), "2");
+    /// let line = Text::from("line\r\n\r\n");
+    /// assert_eq!(line.trim_end_matches("\r\n"), "line");
+    /// ```
+    pub fn trim_end_matches<P: Pattern>(&self, pat: P) -> Text {
+        self.slice_ref(pat.trim_end_matches_of(self))
+    }
+
+    /// An iterator over the chars in a sub-body of text, and their byte
+    /// offsets
+    ///
+    /// The offsets are from the start of the whole text, not the start of
+    /// `r`. Returns `None` if `r` doesn't start and end on char boundaries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("a→b→c");
+    /// let indices: Vec<(usize, char)> = text.char_indices_in(4..).unwrap().collect();
+    /// assert_eq!(indices, [(4, 'b'), (5, '→'), (8, 'c')]);
+    /// assert!(text.char_indices_in(2..).is_none());
+    /// ```
+    pub fn char_indices_in(
+        &self,
+        r: impl RangeBounds<usize>,
+    ) -> Option<impl Iterator<Item = (usize, char)> + '_> {
+        let (start, end) = char_range(self, r)?;
+        Some(
+            self[start..end]
+                .char_indices()
+                .map(move |(i, c)| (start + i, c)),
+        )
+    }
+
+    /// Copies this text into a new buffer of its own
+    ///
+    /// A text made by slicing another one (e.g. with [`Text::get`]) keeps the
+    /// whole original buffer alive, even if it only refers to a tiny part of
+    /// it. Compacting a small, long-lived slice lets the original buffer be
+    /// freed once nothing else uses it.
+    ///
+    /// `Bytes` doesn't expose the size of the buffer it refers to, so this
+    /// can't tell how much memory a slice is holding on to, and always copies
+    /// (except for empty text, which never needs a buffer). It's meant for
+    /// texts known to be small parts of larger buffers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let huge = Text::from("x".repeat(1024 * 1024) + "needle");
+    /// let needle = huge.get(1024 * 1024..).unwrap().compact();
+    /// drop(huge); // the 1 MiB buffer is freed here
+    /// assert_eq!(needle, "needle");
+    /// ```
+    pub fn compact(&self) -> Text {
+        if self.is_empty() {
+            return Text::new();
+        }
+        Text::copy_from(self)
+    }
+
+    /// Splits the text into two halves, returning `(start, end)`
+    ///
+    /// Returns `Err(self)` if the index is not a valid char boundary, so the
+    /// text isn't lost. Use [`Text::split_at_checked`] to get `None` instead,
+    /// or [`Text::split_off`] and [`Text::split_to`] to keep one half in
+    /// place. None of these copy the text.
     ///
     /// # Example
     ///
@@ -224,6 +610,26 @@ impl Text {
         Ok((Self(self.0), Self(right)))
     }
 
+    /// Splits the text into two halves, returning `(start, end)`
+    ///
+    /// This is the same as [`Text::split_at`], except it returns `None` if
+    /// the index is not a valid char boundary. Since texts are cheap to clone,
+    /// call this on a clone if the text is still needed afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("Woo, split!");
+    /// let (a, b) = text.clone().split_at_checked(4).unwrap();
+    /// assert_eq!(a, "Woo,");
+    /// assert_eq!(b, " split!");
+    /// assert!(text.split_at_checked(20).is_none());
+    /// ```
+    pub fn split_at_checked(self, index: usize) -> Option<(Self, Self)> {
+        self.split_at(index).ok()
+    }
+
     /// Splits the text into two halves, `self` being the start half and
     /// returning the end half
     ///
@@ -262,230 +668,3300 @@ impl Text {
     /// ```
     pub fn split_to(&mut self, index: usize) -> Option<Self> {
         soft_assert::soft_assert!(self.is_char_boundary(index));
-        let right = self.0.split_to(index);
-        Some(Self(right))
+        let left = self.0.split_to(index);
+        Some(Self(left))
     }
 
-    fn as_str(&self) -> &str {
-        unsafe { std::str::from_utf8_unchecked(self.0.as_ref()) }
-    }
-}
-
-// ## Conversions
+    /// Escapes this text for use inside a JSON string
+    ///
+    /// Quotes, backslashes, and control chars are escaped as JSON requires,
+    /// and everything else (including non-ASCII chars) is left as-is. The
+    /// surrounding quotes aren't added. If nothing needs escaping, this text
+    /// is returned without copying.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("say \"hi\"\n\tcafé\u{1}");
+    /// assert_eq!(text.escape_json(), r#"say \"hi\"\n\tcafé\u0001"#);
+    /// ```
+    pub fn escape_json(&self) -> Text {
+        use core::fmt::Write;
 
-impl AsRef<str> for Text {
-    fn as_ref(&self) -> &str {
-        self.as_str()
+        let needs_escape = |b: &u8| *b < 0x20 || *b == b'"' || *b == b'\\';
+        if !self.0.iter().any(needs_escape) {
+            return self.clone();
+        }
+        let mut buf = TextMut::with_capacity(self.len() + 8);
+        let mut last = 0;
+        for (i, &b) in self.0.iter().enumerate().filter(|(_, b)| needs_escape(b)) {
+            // every escaped char is a single byte, so these are char boundaries
+            buf.push_str(&self[last..i]);
+            match b {
+                b'"' => buf.push_str("\\\""),
+                b'\\' => buf.push_str("\\\\"),
+                b'\n' => buf.push_str("\\n"),
+                b'\r' => buf.push_str("\\r"),
+                b'\t' => buf.push_str("\\t"),
+                0x08 => buf.push_str("\\b"),
+                0x0c => buf.push_str("\\f"),
+                _ => {
+                    // writing to a `TextMut` can't fail
+                    let _ = write!(buf, "\\u{:04x}", b);
+                }
+            }
+            last = i + 1;
+        }
+        buf.push_str(&self[last..]);
+        buf.freeze()
     }
-}
 
-impl Borrow<str> for Text {
-    fn borrow(&self) -> &str {
-        self.as_str()
+    /// Escapes each char of this text like [`char::escape_default`]
+    ///
+    /// This is the same as [`str::escape_default`], but the result is a
+    /// `Text`. If nothing needs escaping (that is, the text is all printable
+    /// ASCII with no quotes or backslashes), this text is returned without
+    /// copying.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("it's\tnaïve");
+    /// assert_eq!(text.escape_default(), "it\\'s\\tna\\u{ef}ve");
+    /// ```
+    pub fn escape_default(&self) -> Text {
+        let is_plain = |b: &u8| matches!(b, 0x20..=0x7e) && !matches!(b, b'"' | b'\'' | b'\\');
+        if self.0.iter().all(is_plain) {
+            return self.clone();
+        }
+        let mut buf = TextMut::with_capacity(self.len() + 8);
+        buf.extend(str::escape_default(self));
+        buf.freeze()
     }
-}
 
-impl Deref for Text {
-    type Target = str;
+    /// Returns the lowercase equivalent of this text, as a new `TextMut`
+    ///
+    /// This maps chars exactly like [`str::to_lowercase`], including the
+    /// Greek final sigma, and moves the result into the `TextMut` without
+    /// copying it again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("ΟΔΥΣΣΕΥΣ");
+    /// let mut lower = text.to_lowercase();
+    /// lower.push_str("!");
+    /// assert_eq!(lower, "οδυσσευς!");
+    /// ```
+    pub fn to_lowercase(&self) -> TextMut {
+        TextMut::from_string(str::to_lowercase(self))
+    }
 
-    fn deref(&self) -> &Self::Target {
-        self.as_str()
+    /// Returns the uppercase equivalent of this text, as a new `TextMut`
+    ///
+    /// This maps chars exactly like [`str::to_uppercase`], so some chars
+    /// become more than one (like `ß` becoming `SS`), and moves the result
+    /// into the `TextMut` without copying it again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("straße");
+    /// assert_eq!(text.to_uppercase(), "STRASSE");
+    /// ```
+    pub fn to_uppercase(&self) -> TextMut {
+        TextMut::from_string(str::to_uppercase(self))
     }
-}
 
-impl From<&'static str> for Text {
-    fn from(s: &'static str) -> Self {
-        Self::from_static(s)
+    /// Shortens the text to `new_len` bytes
+    ///
+    /// Does nothing if the text is already `new_len` bytes or shorter. The
+    /// rest of the buffer is only freed once no other text refers to it.
+    ///
+    /// Returns `false` and leaves the text unchanged if `new_len` is not a
+    /// valid char boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let mut text = Text::from("née 🦀");
+    /// assert!(text.truncate(4));
+    /// assert_eq!(text, "née");
+    /// assert!(!text.truncate(2));
+    /// ```
+    pub fn truncate(&mut self, new_len: usize) -> bool {
+        if new_len >= self.len() {
+            return true;
+        }
+        soft_assert::soft_assert!(self.is_char_boundary(new_len), false);
+        self.0.truncate(new_len);
+        true
     }
-}
 
-impl From<String> for Text {
+    /// The number of extended grapheme clusters in this text
+    ///
+    /// This is usually what users perceive as the number of "characters",
+    /// and can be lower than the number of `char`s, e.g. for emoji with
+    /// modifiers or letters with combining marks.
+    ///
+    /// Requires the `unicode-segmentation` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("👍🏽!");
+    /// assert_eq!(text.grapheme_count(), 2);
+    /// assert_eq!(text.chars().count(), 3);
+    /// ```
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn grapheme_count(&self) -> usize {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        self.graphemes(true).count()
+    }
+
+    /// Splits the text into two halves at the boundary before the
+    /// `grapheme_index`th grapheme cluster
+    ///
+    /// Unlike [`Text::split_at`], this never cuts a user-perceived character
+    /// (such as an emoji with modifiers, or a letter with combining marks) in
+    /// half.
+    ///
+    /// Returns `Err(self)` if `grapheme_index` is greater than the number of
+    /// grapheme clusters in the text.
+    ///
+    /// Requires the `unicode-segmentation` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("ne\u{301}e");
+    /// let (a, b) = text.split_at_grapheme(2).unwrap();
+    /// assert_eq!(a, "ne\u{301}");
+    /// assert_eq!(b, "e");
+    /// ```
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn split_at_grapheme(self, grapheme_index: usize) -> Result<(Self, Self), Self> {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let index = self
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .chain(core::iter::once(self.len()))
+            .nth(grapheme_index);
+        match index {
+            Some(index) => self.split_at(index),
+            None => Err(self),
+        }
+    }
+
+    /// Returns `true` if the raw bytes of this text start with `prefix`
+    ///
+    /// Unlike [`str::starts_with`], `prefix` doesn't need to be valid UTF-8
+    /// on its own, which is useful for checking magic numbers and partial
+    /// multi-byte sequences.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("%PDF-1.7");
+    /// assert!(text.starts_with_bytes(b"%PDF"));
+    /// assert!(Text::from("é").starts_with_bytes(b"\xc3"));
+    /// ```
+    pub fn starts_with_bytes(&self, prefix: &[u8]) -> bool {
+        self.0.starts_with(prefix)
+    }
+
+    /// Splits the text around the first occurrence of `delim`
+    ///
+    /// Like [`str::split_once`], the delimiter itself is left out of both
+    /// halves. Neither half is copied. `delim` can be any [`Pattern`], such as
+    /// a `char`, a string or a closure.
+    ///
+    /// Returns `None` if `delim` isn't found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("key=value=more");
+    /// let (key, value) = text.split_once('=').unwrap();
+    /// assert_eq!(key, "key");
+    /// assert_eq!(value, "value=more");
+    ///
+    /// let header = Text::from("Host: example.com: 8080");
+    /// let (name, value) = header.split_once(": ").unwrap();
+    /// assert_eq!(name, "Host");
+    /// assert_eq!(value, "example.com: 8080");
+    /// ```
+    pub fn split_once<P: Pattern>(&self, mut delim: P) -> Option<(Text, Text)> {
+        let (start, end) = delim.next_match(self)?;
+        Some((self.slice_ref(&self[..start]), self.slice_ref(&self[end..])))
+    }
+
+    /// Splits the text around the last occurrence of `delim`
+    ///
+    /// Like [`str::rsplit_once`], the delimiter itself is left out of both
+    /// halves. Neither half is copied. `delim` can be any [`Pattern`], such as
+    /// a `char`, a string or a closure.
+    ///
+    /// Returns `None` if `delim` isn't found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("archive.tar.gz");
+    /// let (stem, ext) = text.rsplit_once('.').unwrap();
+    /// assert_eq!(stem, "archive.tar");
+    /// assert_eq!(ext, "gz");
+    ///
+    /// let path = Text::from("a::b::c");
+    /// let (module, item) = path.rsplit_once("::").unwrap();
+    /// assert_eq!(module, "a::b");
+    /// assert_eq!(item, "c");
+    /// ```
+    pub fn rsplit_once<P: Pattern>(&self, mut delim: P) -> Option<(Text, Text)> {
+        let (start, end) = delim.next_match_back(self)?;
+        Some((self.slice_ref(&self[..start]), self.slice_ref(&self[end..])))
+    }
+
+    /// Splits the text around the first occurrence of the raw bytes `delim`
+    ///
+    /// The delimiter itself is left out of both halves, and neither half is
+    /// copied. `delim` doesn't need to be valid UTF-8, but both sides of the
+    /// match need to land on char boundaries.
+    ///
+    /// Returns `None` if `delim` isn't found, or if the match doesn't start
+    /// and end on char boundaries. An empty `delim` matches at the start.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("Host: example.com");
+    /// let (name, value) = text.split_once_slice(b": ").unwrap();
+    /// assert_eq!(name, "Host");
+    /// assert_eq!(value, "example.com");
+    /// ```
+    pub fn split_once_slice(&self, delim: &[u8]) -> Option<(Text, Text)> {
+        let start = if delim.is_empty() {
+            0
+        } else {
+            self.0.windows(delim.len()).position(|w| w == delim)?
+        };
+        let end = start + delim.len();
+        // the match could start or end partway through a char if `delim` is
+        // only part of a multi-byte sequence, in which case every other match
+        // would too
+        soft_assert::soft_assert!(self.is_char_boundary(start) && self.is_char_boundary(end));
+        Some((Self(self.0.slice(..start)), Self(self.0.slice(end..))))
+    }
+
+    /// Splits the text into the longest prefix that is at most `max_bytes`
+    /// long, and the rest
+    ///
+    /// If `max_bytes` lands inside a multi-byte char, that char is put in the
+    /// second half. Neither half is copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("café!");
+    /// // `é` is 2 bytes long, starting at byte 3
+    /// let (a, b) = text.split_within_bytes(4);
+    /// assert_eq!(a, "caf");
+    /// assert_eq!(b, "é!");
+    /// ```
+    pub fn split_within_bytes(&self, max_bytes: usize) -> (Text, Text) {
+        let mut index = max_bytes.min(self.len());
+        while !self.is_char_boundary(index) {
+            index -= 1;
+        }
+        (Self(self.0.slice(..index)), Self(self.0.slice(index..)))
+    }
+
+    /// Gets a view of this text for fast byte-indexed operations, if it is
+    /// all ASCII
+    ///
+    /// Returns `None` if the text contains any non-ASCII chars.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("abc");
+    /// assert_eq!(text.as_ascii().unwrap().char_at(2), 'c');
+    /// assert!(Text::from("äbc").as_ascii().is_none());
+    /// ```
+    pub fn as_ascii(&self) -> Option<AsciiText<'_>> {
+        AsciiText::new(self)
+    }
+
+    /// Checks that this text and `other` are equal, ignoring ASCII case
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("Content-Type");
+    /// assert!(text.eq_ignore_ascii_case("CONTENT-type"));
+    /// assert!(!text.eq_ignore_ascii_case("Content-Length"));
+    /// ```
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.0.eq_ignore_ascii_case(other.as_bytes())
+    }
+
+    /// Checks that this text and the raw bytes `other` are equal, ignoring
+    /// ASCII case
+    ///
+    /// `other` doesn't need to be valid UTF-8, which makes this handy for
+    /// comparing against byte string constants.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let name = Text::from("Content-Type");
+    /// assert!(name.eq_ignore_ascii_case_bytes(b"content-type"));
+    /// assert!(!name.eq_ignore_ascii_case_bytes(b"content-length"));
+    /// ```
+    pub fn eq_ignore_ascii_case_bytes(&self, other: &[u8]) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+
+    /// Checks that this text and `other` are equal, ignoring case
+    ///
+    /// This compares the texts char by char using Unicode simple case
+    /// folding, so each char only ever folds to a single char. Chars that
+    /// only have a multi-char folding, like `ß` (which would fully fold to
+    /// `ss`), are compared as-is. No language-specific tailoring (like the
+    /// Turkish dotless `ı`) is applied.
+    ///
+    /// Requires the `unicode-case` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("Straße nach Αθήνα");
+    /// assert!(text.eq_ignore_case("STRAẞE NACH ΑΘΉΝΑ"));
+    /// assert!(!text.eq_ignore_case("STRASSE NACH ΑΘΉΝΑ"));
+    /// ```
+    #[cfg(feature = "unicode-case")]
+    pub fn eq_ignore_case(&self, other: &str) -> bool {
+        fn fold(c: char) -> char {
+            unicode_case_mapping::case_folded(c)
+                .and_then(|folded| char::from_u32(folded.get()))
+                .unwrap_or(c)
+        }
+
+        self.chars().map(fold).eq(other.chars().map(fold))
+    }
+
+    /// Returns the rest of the text after `prefix`
+    ///
+    /// Returns `None` if the text doesn't start with `prefix`. The returned
+    /// text is not copied. Like [`str::strip_prefix`], `prefix` can be any
+    /// [`Pattern`], such as a `char` or a closure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let line = Text::from("PING :irc.example.com");
+    /// assert_eq!(line.strip_prefix("PING :").unwrap(), "irc.example.com");
+    /// assert!(line.strip_prefix("PONG").is_none());
+    /// assert_eq!(line.strip_prefix('P').unwrap(), "ING :irc.example.com");
+    /// ```
+    pub fn strip_prefix<P: Pattern>(&self, prefix: P) -> Option<Text> {
+        prefix
+            .strip_prefix_of(self)
+            .map(|rest| self.slice_ref(rest))
+    }
+
+    /// Returns the rest of the text before `suffix`
+    ///
+    /// Returns `None` if the text doesn't end with `suffix`. The returned
+    /// text is not copied. Like [`str::strip_suffix`], `suffix` can be any
+    /// [`Pattern`], such as a `char` or a closure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let line = Text::from("HELLO\r\n");
+    /// assert_eq!(line.strip_suffix("\r\n").unwrap(), "HELLO");
+    /// assert!(line.strip_suffix("\n\n").is_none());
+    /// assert_eq!(line.strip_suffix('\n').unwrap(), "HELLO\r");
+    /// ```
+    pub fn strip_suffix<P: Pattern>(&self, suffix: P) -> Option<Text> {
+        suffix
+            .strip_suffix_of(self)
+            .map(|rest| self.slice_ref(rest))
+    }
+
+    /// Returns the rest of the text after `prefix`, ignoring ASCII case when
+    /// matching the prefix
+    ///
+    /// Returns `None` if the text doesn't start with `prefix`. The returned
+    /// text is not copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let header = Text::from("bearer abc123");
+    /// let token = header.strip_prefix_ignore_ascii_case("Bearer ").unwrap();
+    /// assert_eq!(token, "abc123");
+    /// assert!(header.strip_prefix_ignore_ascii_case("Basic ").is_none());
+    /// ```
+    pub fn strip_prefix_ignore_ascii_case(&self, prefix: &str) -> Option<Text> {
+        let start = self.0.get(..prefix.len())?;
+        soft_assert::soft_assert!(start.eq_ignore_ascii_case(prefix.as_bytes()));
+        // ASCII case-insensitivity never matches a non-ASCII byte against an
+        // ASCII one, so this is guaranteed to be a char boundary
+        Some(Self(self.0.slice(prefix.len()..)))
+    }
+
+    /// The number of whole chars that fit within the first `max_bytes` bytes
+    ///
+    /// A char that starts before `max_bytes` but ends after it is not
+    /// counted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("naïve");
+    /// // `ï` is 2 bytes long, starting at byte 2
+    /// assert_eq!(text.chars_in_first_bytes(3), 2);
+    /// assert_eq!(text.chars_in_first_bytes(4), 3);
+    /// ```
+    pub fn chars_in_first_bytes(&self, max_bytes: usize) -> usize {
+        self.char_indices()
+            .take_while(|&(i, c)| i + c.len_utf8() <= max_bytes)
+            .count()
+    }
+
+    /// Gets the char at byte `index` along with the byte range it occupies
+    ///
+    /// If `index` lands inside a multi-byte char, it is snapped down to the
+    /// start of that char. Returns `None` if `index` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("a€b");
+    /// // `€` is 3 bytes long, starting at byte 1
+    /// assert_eq!(text.char_and_span(2), Some(('€', 1..4)));
+    /// assert_eq!(text.char_and_span(4), Some(('b', 4..5)));
+    /// assert_eq!(text.char_and_span(5), None);
+    /// ```
+    pub fn char_and_span(&self, index: usize) -> Option<(char, Range<usize>)> {
+        soft_assert::soft_assert!(index < self.len());
+        let mut start = index;
+        while !self.is_char_boundary(start) {
+            start -= 1;
+        }
+        let c = self[start..].chars().next()?;
+        Some((c, start..start + c.len_utf8()))
+    }
+
+    /// Returns the first char of the text, or `None` if it's empty
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// assert_eq!(Text::from("éa").first_char(), Some('é'));
+    /// assert_eq!(Text::new().first_char(), None);
+    /// ```
+    pub fn first_char(&self) -> Option<char> {
+        self.chars().next()
+    }
+
+    /// Returns the last char of the text, or `None` if it's empty
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// assert_eq!(Text::from("aé").last_char(), Some('é'));
+    /// assert_eq!(Text::new().last_char(), None);
+    /// ```
+    pub fn last_char(&self) -> Option<char> {
+        self.chars().next_back()
+    }
+
+    /// An iterator over the UTF-16 code units of this text
+    ///
+    /// This is the same as [`str::encode_utf16`], and doesn't allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("a🌍");
+    /// let units: Vec<u16> = text.encode_utf16().collect();
+    /// assert_eq!(units, [0x61, 0xd83c, 0xdf0d]);
+    /// ```
+    pub fn encode_utf16(&self) -> EncodeUtf16<'_> {
+        (**self).encode_utf16()
+    }
+
+    /// The length of this text in UTF-16 code units
+    ///
+    /// This is what [`Text::encode_utf16`] would yield, but it's counted
+    /// straight from the UTF-8 bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("a€🌍");
+    /// assert_eq!(text.len(), 8);
+    /// assert_eq!(text.utf16_len(), 4);
+    /// ```
+    pub fn utf16_len(&self) -> usize {
+        Self::utf16_len_of(&self.0)
+    }
+
+    /// Converts a UTF-16 code unit offset into a byte offset
+    ///
+    /// Returns `None` if `utf16_offset` is past the end of the text, or lands
+    /// between the two halves of a surrogate pair.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("🌍 hi");
+    /// assert_eq!(text.utf16_to_byte(3), Some(5));
+    /// assert_eq!(text.utf16_to_byte(1), None);
+    /// ```
+    pub fn utf16_to_byte(&self, utf16_offset: usize) -> Option<usize> {
+        let mut units = 0;
+        for (i, c) in self.char_indices() {
+            if units == utf16_offset {
+                return Some(i);
+            }
+            units += c.len_utf16();
+            soft_assert::soft_assert!(units <= utf16_offset);
+        }
+        soft_assert::soft_assert!(units == utf16_offset);
+        Some(self.len())
+    }
+
+    /// Converts a byte offset into a UTF-16 code unit offset
+    ///
+    /// Returns `None` if `byte_offset` is past the end of the text, or isn't
+    /// a char boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("🌍 hi");
+    /// assert_eq!(text.byte_to_utf16(5), Some(3));
+    /// assert_eq!(text.byte_to_utf16(1), None);
+    /// ```
+    pub fn byte_to_utf16(&self, byte_offset: usize) -> Option<usize> {
+        soft_assert::soft_assert!(self.is_char_boundary(byte_offset));
+        Some(Self::utf16_len_of(&self.0[..byte_offset]))
+    }
+
+    /// An iterator over the chars of this text, each as its own `Text`, along
+    /// with the byte offset it starts at
+    ///
+    /// The offsets match [`str::char_indices`], but rather than a `char`, each
+    /// item is a `Text` that shares this text's buffer, so it can be kept
+    /// around on its own.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("hé🦀");
+    /// let chars: Vec<(usize, Text)> = text.char_slices().collect();
+    /// assert_eq!(chars, [(0, "h".into()), (1, "é".into()), (3, "🦀".into())]);
+    /// ```
+    pub fn char_slices(&self) -> CharSlices {
+        CharSlices::new(self.clone())
+    }
+
+    /// An iterator over every byte offset that is a char boundary, including
+    /// `0` and [`len`](Text::len)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("aé€");
+    /// let boundaries: Vec<usize> = text.char_boundaries().collect();
+    /// assert_eq!(boundaries, [0, 1, 3, 6]);
+    /// ```
+    pub fn char_boundaries(&self) -> impl Iterator<Item = usize> + '_ {
+        self.char_indices()
+            .map(|(i, _)| i)
+            .chain(core::iter::once(self.len()))
+    }
+
+    /// Joins the texts from an iterator together, with `sep` between each one
+    ///
+    /// If the iterator yields only a single text, it is returned as-is
+    /// without copying.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let parts = vec![Text::from("a"), Text::from("b"), Text::from("c")];
+    /// let joined = Text::join_iter(parts, ", ");
+    /// assert_eq!(joined, "a, b, c");
+    ///
+    /// let empty = Text::join_iter(Vec::new(), ", ");
+    /// assert!(empty.is_empty());
+    /// ```
+    pub fn join_iter<I: IntoIterator<Item = Text>>(iter: I, sep: &str) -> Text {
+        let mut iter = iter.into_iter();
+        let first = match iter.next() {
+            Some(first) => first,
+            None => return Text::new(),
+        };
+        let second = match iter.next() {
+            Some(second) => second,
+            None => return first,
+        };
+        let (lower_bound, _) = iter.size_hint();
+        let mut buf = TextMut::with_capacity(first.len() + sep.len() + second.len());
+        buf.reserve(lower_bound * sep.len());
+        buf.push_str(first);
+        buf.push_str(sep);
+        buf.push_str(second);
+        for text in iter {
+            buf.push_str(sep);
+            buf.push_str(text);
+        }
+        buf.freeze()
+    }
+
+    /// Concatenates `parts` into a single text
+    ///
+    /// The total length is added up first so that the result is allocated
+    /// only once. If there's only a single part, it's returned without
+    /// copying. See [`Text::try_concat`] for a version that doesn't panic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total length would overflow `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let parts = [Text::from("Hello"), Text::from(", "), Text::from("world")];
+    /// assert_eq!(Text::concat(&parts), "Hello, world");
+    /// assert_eq!(Text::concat(&[]), "");
+    /// ```
+    pub fn concat(parts: &[Text]) -> Text {
+        Self::try_concat(parts).expect("capacity overflow")
+    }
+
+    /// Concatenates the texts from an iterator into a single text
+    ///
+    /// This is the same as [`Text::join_iter`] with an empty separator, so if
+    /// the iterator yields only a single text, it is returned without copying.
+    /// Prefer [`Text::concat`] when the parts are already in a slice, since it
+    /// can allocate the exact size up front.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let words = Text::from("one two three");
+    /// let squashed = Text::concat_iter(words.split(' '));
+    /// assert_eq!(squashed, "onetwothree");
+    /// ```
+    pub fn concat_iter<I: IntoIterator<Item = Text>>(iter: I) -> Text {
+        Self::join_iter(iter, "")
+    }
+
+    /// Concatenates `parts` into a single text, or returns `None` if their
+    /// total length would overflow `usize`
+    ///
+    /// The total length is checked before anything is allocated, so this
+    /// never panics on untrusted input. If there's only a single part, it's
+    /// returned without copying.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let parts = [Text::from("foo"), Text::from("bar"), Text::from("baz")];
+    /// assert_eq!(Text::try_concat(&parts).unwrap(), "foobarbaz");
+    /// ```
+    pub fn try_concat(parts: &[Text]) -> Option<Text> {
+        let len = Self::total_len(parts.iter().map(Text::len))?;
+        if let [part] = parts {
+            return Some(part.clone());
+        }
+        let mut buf = TextMut::with_capacity(len);
+        for part in parts {
+            buf.push_str(part);
+        }
+        Some(buf.freeze())
+    }
+
+    /// Repeats this text `n` times
+    ///
+    /// Unlike [`str::repeat`], this builds a `Text` directly instead of
+    /// going through a `String`. Repeating once returns this text without
+    /// copying it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the result would overflow `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let rule = Text::from("-").repeat(8);
+    /// assert_eq!(rule, "--------");
+    /// ```
+    pub fn repeat(&self, n: usize) -> Text {
+        self.repeat_join(n, "")
+    }
+
+    /// Repeats this text `n` times, with `sep` between each copy
+    ///
+    /// The whole result is allocated up front. Repeating once returns this
+    /// text without copying it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the result would overflow `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("ab");
+    /// assert_eq!(text.repeat_join(3, "-"), "ab-ab-ab");
+    /// assert_eq!(text.repeat_join(0, "-"), "");
+    /// ```
+    pub fn repeat_join(&self, n: usize, sep: &str) -> Text {
+        match n {
+            0 => return Text::new(),
+            1 => return self.clone(),
+            _ => {}
+        }
+        let len = self
+            .len()
+            .checked_mul(n)
+            .zip(sep.len().checked_mul(n - 1))
+            .and_then(|(texts, seps)| texts.checked_add(seps))
+            .expect("capacity overflow");
+        let mut buf = TextMut::with_capacity(len);
+        buf.push_str(self);
+        for _ in 1..n {
+            buf.push_str(sep);
+            buf.push_str(self);
+        }
+        buf.freeze()
+    }
+
+    /// Splits the text at each of the given char indices
+    ///
+    /// `char_indices` must be in ascending order, and each one can be at most
+    /// the number of chars in the text. Splitting at `n` cuts points gives
+    /// `n + 1` texts, none of which are copied.
+    ///
+    /// Returns `None` if the indices are out of order or out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("año2024€");
+    /// let parts = text.split_at_many(&[3, 7]).unwrap();
+    /// assert_eq!(parts, ["año", "2024", "€"]);
+    /// ```
+    pub fn split_at_many(&self, char_indices: &[usize]) -> Option<Vec<Text>> {
+        // the byte offset of every char boundary, including the end
+        let mut boundaries = self
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(core::iter::once(self.len()));
+        // the char index of the next boundary `boundaries` will yield
+        let mut next_char_index = 0;
+        let mut prev = (0, 0);
+        let mut parts = Vec::with_capacity(char_indices.len() + 1);
+        for &char_index in char_indices {
+            let (prev_char_index, start) = prev;
+            let end = if char_index == prev_char_index {
+                start
+            } else {
+                soft_assert::soft_assert!(char_index > prev_char_index);
+                let end = boundaries.nth(char_index - next_char_index)?;
+                next_char_index = char_index + 1;
+                end
+            };
+            parts.push(Self(self.0.slice(start..end)));
+            prev = (char_index, end);
+        }
+        parts.push(Self(self.0.slice(prev.1..)));
+        Some(parts)
+    }
+
+    /// Splits the text into one text per char
+    ///
+    /// Unlike [`str::chars`], each char is kept as a slice of the original
+    /// buffer, so none of them are copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("añ🦀");
+    /// let chars = text.into_chars_text();
+    /// assert_eq!(chars, ["a", "ñ", "🦀"]);
+    /// ```
+    pub fn into_chars_text(self) -> Vec<Text> {
+        self.char_indices()
+            .map(|(i, c)| Self(self.0.slice(i..i + c.len_utf8())))
+            .collect()
+    }
+
+    /// Finds the longest prefix shared by all of the given texts
+    ///
+    /// The returned text is a slice of the first text, so it is not copied.
+    /// If `texts` is empty, an empty text is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let texts = [Text::from("interstellar"), Text::from("internet"), Text::from("interval")];
+    /// assert_eq!(Text::common_prefix_of(&texts), "inter");
+    /// ```
+    pub fn common_prefix_of(texts: &[Text]) -> Text {
+        let (first, rest) = match texts.split_first() {
+            Some(split) => split,
+            None => return Text::new(),
+        };
+        let mut len = rest.iter().fold(first.len(), |len, text| {
+            first.0[..len]
+                .iter()
+                .zip(text.0.iter())
+                .take_while(|(a, b)| a == b)
+                .count()
+        });
+        // the prefix could end partway through a char that differs in its
+        // later bytes
+        while !first.is_char_boundary(len) {
+            len -= 1;
+        }
+        Self(first.0.slice(..len))
+    }
+
+    /// An iterator over the fields of this text, separated by `delim`, where
+    /// fields can be quoted with `quote` to include `delim` in them
+    ///
+    /// A field is quoted if it starts with `quote`, and ends at the next
+    /// lone `quote`. Inside a quoted field, a doubled quote (e.g. `""`) stands
+    /// for a single literal quote. Anything between the closing quote and the
+    /// next `delim` is kept as-is, and a quoted field that is never closed
+    /// runs to the end of the text. There is no other escaping.
+    ///
+    /// Like [`str::split`], an empty text has one empty field, and a trailing
+    /// `delim` is followed by an empty field.
+    ///
+    /// Fields are slices of this text, except for quoted fields containing
+    /// doubled quotes, which have to be copied to remove the extra quotes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let row = Text::from(r#"1,"Smith, Jo","say ""hi""",x"#);
+    /// let fields: Vec<Text> = row.quoted_fields(',', '"').collect();
+    /// assert_eq!(fields, ["1", "Smith, Jo", r#"say "hi""#, "x"]);
+    /// ```
+    pub fn quoted_fields(&self, delim: char, quote: char) -> QuotedFields {
+        QuotedFields::new(self.clone(), delim, quote)
+    }
+
+    /// An iterator over the fields of this text separated by `delim`, along
+    /// with the index of each field
+    ///
+    /// Fields are split like [`str::split`], and none of them are copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let record = Text::from("2024-01-05|INFO|server started|main");
+    /// let (_, message) = record
+    ///     .split_enumerate('|')
+    ///     .find(|(i, _)| *i == 2)
+    ///     .unwrap();
+    /// assert_eq!(message, "server started");
+    /// ```
+    pub fn split_enumerate(&self, delim: char) -> impl Iterator<Item = (usize, Text)> + '_ {
+        self.split(delim).enumerate()
+    }
+
+    /// An iterator over the parts of this text separated by `delim`
+    ///
+    /// This matches [`str::split`], including the empty parts before a
+    /// leading delimiter or after a trailing one, but each part is a `Text`
+    /// that shares this text's buffer. `delim` can be any [`Pattern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from(",a,,b,");
+    /// let parts: Vec<Text> = text.split(',').collect();
+    /// assert_eq!(parts, ["", "a", "", "b", ""]);
+    ///
+    /// let list = Text::from("one, two, three");
+    /// let items: Vec<Text> = list.split(", ").collect();
+    /// assert_eq!(items, ["one", "two", "three"]);
+    /// ```
+    pub fn split<P: Pattern>(&self, delim: P) -> Split<P> {
+        Split::new(self.clone(), delim)
+    }
+
+    /// An iterator over at most `n` parts of this text separated by `delim`
+    ///
+    /// This matches [`str::splitn`]: the last part holds the rest of the text,
+    /// delimiters and all. `delim` can be any [`Pattern`], and none of the
+    /// parts are copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("key=value=more");
+    /// let parts: Vec<Text> = text.splitn(2, '=').collect();
+    /// assert_eq!(parts, ["key", "value=more"]);
+    /// ```
+    pub fn splitn<P: Pattern>(&self, n: usize, delim: P) -> SplitN<P> {
+        SplitN::new(self.clone(), n, delim)
+    }
+
+    /// An iterator over the parts of this text separated by `delim`, starting
+    /// from the end
+    ///
+    /// This matches [`str::rsplit`]. `delim` can be any [`Pattern`], and
+    /// none of the parts are copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("www.example.com");
+    /// let labels: Vec<Text> = text.rsplit('.').collect();
+    /// assert_eq!(labels, ["com", "example", "www"]);
+    ///
+    /// let path = Text::from("crate::module::Item");
+    /// assert_eq!(path.rsplit("::").next().unwrap(), "Item");
+    /// ```
+    pub fn rsplit<P: Pattern>(&self, delim: P) -> RSplit<P> {
+        RSplit::new(self.clone(), delim)
+    }
+
+    /// An iterator over at most `n` parts of this text separated by `delim`,
+    /// starting from the end
+    ///
+    /// This matches [`str::rsplitn`]: the last part holds the rest of the
+    /// start of the text, delimiters and all. `delim` can be any
+    /// [`Pattern`], and none of the parts are copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("crate::module::Item");
+    /// let mut parts = text.rsplitn(2, ':');
+    /// assert_eq!(parts.next().unwrap(), "Item");
+    /// assert_eq!(parts.next().unwrap(), "crate::module:");
+    /// assert!(parts.next().is_none());
+    ///
+    /// let mut parts = text.rsplitn(2, "::");
+    /// assert_eq!(parts.next().unwrap(), "Item");
+    /// assert_eq!(parts.next().unwrap(), "crate::module");
+    /// ```
+    pub fn rsplitn<P: Pattern>(&self, n: usize, delim: P) -> RSplitN<P> {
+        RSplitN::new(self.clone(), n, delim)
+    }
+
+    /// An iterator over the whitespace-separated tokens of this text
+    ///
+    /// Tokens are split like [`str::split_whitespace`], skipping leading,
+    /// trailing, and repeated whitespace, but each token is a `Text` that
+    /// shares this text's buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("  let\u{3000}x =\t42; ");
+    /// let tokens: Vec<Text> = text.split_whitespace().collect();
+    /// assert_eq!(tokens, ["let", "x", "=", "42;"]);
+    /// ```
+    pub fn split_whitespace(&self) -> SplitWhitespace {
+        SplitWhitespace::new(self.clone())
+    }
+
+    /// An iterator over the ASCII-whitespace-separated tokens of this text
+    ///
+    /// Tokens are split like [`str::split_ascii_whitespace`], so non-ASCII
+    /// whitespace is kept as part of the tokens. None of the tokens are
+    /// copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("GET /index.html\tHTTP/1.1\r\n");
+    /// let tokens: Vec<Text> = text.split_ascii_whitespace().collect();
+    /// assert_eq!(tokens, ["GET", "/index.html", "HTTP/1.1"]);
+    /// ```
+    pub fn split_ascii_whitespace(&self) -> SplitAsciiWhitespace {
+        SplitAsciiWhitespace::new(self.clone())
+    }
+
+    /// An iterator over the whitespace-separated tokens of this text, along
+    /// with the byte offset each one starts at
+    ///
+    /// Tokens are split like [`str::split_whitespace`], and none of them are
+    /// copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("  let x =\t42;");
+    /// let tokens: Vec<(usize, Text)> = text.split_whitespace_indices().collect();
+    /// assert_eq!(tokens[0], (2, "let".into()));
+    /// assert_eq!(tokens[3], (10, "42;".into()));
+    /// ```
+    pub fn split_whitespace_indices(&self) -> impl Iterator<Item = (usize, Text)> + '_ {
+        let start = self.as_ptr() as usize;
+        str::split_whitespace(self)
+            .map(move |token| (token.as_ptr() as usize - start, self.slice_ref(token)))
+    }
+
+    /// An iterator over the parts of this text separated by any of the
+    /// delimiters in `delims`
+    ///
+    /// At each position, the longest delimiter that matches there wins, so
+    /// `["\r", "\r\n"]` splits `"a\r\nb"` into `"a"` and `"b"`, rather than
+    /// leaving an empty part in between. Empty delimiters are ignored. Like
+    /// [`str::split`], a delimiter at the very end gives an empty last part.
+    /// None of the parts are copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("one\r\ntwo\rthree\nfour");
+    /// let lines: Vec<Text> = text.split_any(&["\r\n", "\n", "\r"]).collect();
+    /// assert_eq!(lines, ["one", "two", "three", "four"]);
+    /// ```
+    pub fn split_any<'a>(&self, delims: &'a [&'a str]) -> SplitAny<'a> {
+        SplitAny::new(self.clone(), delims)
+    }
+
+    /// An iterator over the lines of this text
+    ///
+    /// Lines are split exactly like [`str::lines`]: on `"\n"` or `"\r\n"`,
+    /// with no empty last line after a final line ending. Unlike
+    /// [`str::lines`], each line is a `Text` that shares this text's buffer,
+    /// so it can outlive the borrow of `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("GET / HTTP/1.1\r\nHost: example.com\r\n");
+    /// let lines: Vec<Text> = text.lines().collect();
+    /// assert_eq!(lines, ["GET / HTTP/1.1", "Host: example.com"]);
+    /// ```
+    pub fn lines(&self) -> Lines {
+        Lines::new(self.clone())
+    }
+
+    /// An iterator over the lines of this text, along with the line ending
+    /// after each one
+    ///
+    /// Lines are split like [`str::lines`], but each one comes with its
+    /// line ending: `"\n"`, `"\r\n"`, or empty for a last line with no line
+    /// ending. Putting them all back together gives back the original text.
+    /// Neither the lines nor the endings are copied, and lines can be taken
+    /// from either end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("one\r\ntwo\nthree");
+    /// let lines: Vec<(Text, Text)> = text.lines_with_endings().collect();
+    /// assert_eq!(lines[0], ("one".into(), "\r\n".into()));
+    /// assert_eq!(lines[1], ("two".into(), "\n".into()));
+    /// assert_eq!(lines[2], ("three".into(), "".into()));
+    ///
+    /// let (last, _) = text.lines_with_endings().next_back().unwrap();
+    /// assert_eq!(last, "three");
+    /// ```
+    pub fn lines_with_endings(&self) -> LinesWithEndings {
+        LinesWithEndings::new(self.clone())
+    }
+
+    /// Returns the `n`th line of this text (counting from zero), without its
+    /// line ending
+    ///
+    /// Lines are split like [`str::lines`], and the returned line isn't
+    /// copied. This still has to scan the text up to the line, so it takes
+    /// O(n) time in the position of the line.
+    ///
+    /// Returns `None` if there are `n` lines or fewer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("first\r\nsecond\nthird");
+    /// assert_eq!(text.nth_line(1).unwrap(), "second");
+    /// assert!(text.nth_line(3).is_none());
+    /// ```
+    pub fn nth_line(&self, n: usize) -> Option<Text> {
+        self.lines().nth(n)
+    }
+
+    /// Finds the start of the line containing `byte_offset`
+    ///
+    /// This is the offset just after the last `\n` before `byte_offset`, or
+    /// `0` if there isn't one. Returns `None` if `byte_offset` isn't a char
+    /// boundary, or is past the end of the text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("first\nsecond\nthird");
+    /// assert_eq!(text.line_start(9), Some(6));
+    /// assert_eq!(text.line_start(3), Some(0));
+    /// ```
+    pub fn line_start(&self, byte_offset: usize) -> Option<usize> {
+        soft_assert::soft_assert!(self.is_char_boundary(byte_offset));
+        Some(self[..byte_offset].rfind('\n').map_or(0, |i| i + 1))
+    }
+
+    /// Finds the end of the line containing `byte_offset`
+    ///
+    /// This is the offset of the first `\n` at or after `byte_offset`, or the
+    /// length of the text if there isn't one. Returns `None` if `byte_offset`
+    /// isn't a char boundary, or is past the end of the text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("first\nsecond\nthird");
+    /// assert_eq!(text.line_end(9), Some(12));
+    /// assert_eq!(text.line_end(15), Some(18));
+    /// ```
+    pub fn line_end(&self, byte_offset: usize) -> Option<usize> {
+        soft_assert::soft_assert!(self.is_char_boundary(byte_offset));
+        Some(
+            self[byte_offset..]
+                .find('\n')
+                .map_or(self.len(), |i| byte_offset + i),
+        )
+    }
+
+    /// Keeps only the lines for which `f` returns `true`
+    ///
+    /// Lines are split like [`Text::lines_with_endings`], and each kept line
+    /// keeps its original line ending. If every line is kept, this is a cheap
+    /// clone of the text. Otherwise, copying starts at the first dropped
+    /// line, and everything after it that's kept is copied into a new buffer
+    /// along with the lines before it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let log = Text::from("ok\nerror: disk full\nok\nerror: timeout\n");
+    /// let errors = log.filter_lines(|line| line.starts_with("error"));
+    /// assert_eq!(errors, "error: disk full\nerror: timeout\n");
+    /// ```
+    pub fn filter_lines<F: FnMut(&str) -> bool>(&self, mut f: F) -> Text {
+        let start = self.as_ptr() as usize;
+        // only allocated once a line is dropped
+        let mut kept: Option<TextMut> = None;
+        for (line, ending) in self.lines_with_endings() {
+            let keep = f(&line);
+            match &mut kept {
+                Some(kept) if keep => {
+                    kept.push_str(&line);
+                    kept.push_str(&ending);
+                }
+                Some(_) => {}
+                None if keep => {}
+                None => {
+                    let line_start = line.as_ptr() as usize - start;
+                    let mut seed = TextMut::with_capacity(self.len() - line.len());
+                    seed.push_str(&self[..line_start]);
+                    kept = Some(seed);
+                }
+            }
+        }
+        kept.map_or_else(|| self.clone(), TextMut::freeze)
+    }
+
+    /// Feeds the raw bytes of this text into `hasher`
+    ///
+    /// This makes exactly one call to [`Hasher::write`] with the UTF-8 bytes
+    /// of the text, and nothing else. That differs from the [`Hash`] impl,
+    /// which hashes like a `str` and so also writes a terminator byte. Use this
+    /// when the digest needs to match hashing the same bytes elsewhere, like
+    /// in a content-addressed cache.
+    ///
+    /// [`Hasher::write`]: std::hash::Hasher::write
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::Hasher;
+    ///
+    /// let mut a = DefaultHasher::new();
+    /// Text::from("hello").content_hash(&mut a);
+    ///
+    /// let mut b = DefaultHasher::new();
+    /// b.write(b"hello");
+    ///
+    /// assert_eq!(a.finish(), b.finish());
+    /// ```
+    pub fn content_hash<H: core::hash::Hasher>(&self, hasher: &mut H) {
+        hasher.write(&self.0);
+    }
+
+    /// Renders the raw bytes of this text as printable ASCII
+    ///
+    /// Printable ASCII bytes are kept as-is, except for `\`, which is
+    /// rendered as `\\` so that the output stays unambiguous. Every other
+    /// byte (control characters and each byte of a multi-byte char) is
+    /// rendered as a `\xNN` escape.
+    ///
+    /// Unlike [`str::escape_debug`], this works byte-by-byte rather than
+    /// char-by-char.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("ok\r\n\té");
+    /// assert_eq!(text.debug_bytes(), "ok\\x0d\\x0a\\x09\\xc3\\xa9");
+    /// ```
+    pub fn debug_bytes(&self) -> Text {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        let mut buf = TextMut::with_capacity(self.len());
+        for &b in self.0.iter() {
+            match b {
+                b'\\' => buf.push_str("\\\\"),
+                0x20..=0x7e => buf.push(b as char),
+                _ => {
+                    buf.push_str("\\x");
+                    buf.push(HEX[(b >> 4) as usize] as char);
+                    buf.push(HEX[(b & 0xf) as usize] as char);
+                }
+            }
+        }
+        buf.freeze()
+    }
+
+    /// Renders the raw bytes of this text as a hexdump
+    ///
+    /// Each line shows 16 bytes in the form `offset: hex  ascii`, where
+    /// `offset` is the offset of the first byte on the line (as 8 hex digits)
+    /// and non-printable bytes are shown as `.` in the ascii column.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("Hello, world!\n");
+    /// assert_eq!(
+    ///     text.hexdump(),
+    ///     "00000000: 48 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 0a        Hello, world!.\n"
+    /// );
+    /// ```
+    pub fn hexdump(&self) -> Text {
+        use core::fmt::Write;
+
+        let mut out = String::new();
+        for (line, chunk) in self.0.chunks(16).enumerate() {
+            // writing to a `String` can't fail
+            let _ = write!(out, "{:08x}:", line * 16);
+            for b in chunk {
+                let _ = write!(out, " {:02x}", b);
+            }
+            for _ in chunk.len()..16 {
+                out.push_str("   ");
+            }
+            out.push_str("  ");
+            out.extend(chunk.iter().map(|&b| match b {
+                0x20..=0x7e => b as char,
+                _ => '.',
+            }));
+            out.push('\n');
+        }
+        Text::from(out)
+    }
+
+    /// Counts how many times the byte `b` appears in this text
+    ///
+    /// With the `memchr` feature enabled, this uses SIMD where it's available,
+    /// which is much faster for large texts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("one\ntwo\nthree\n");
+    /// assert_eq!(text.count_byte(b'\n'), 3);
+    /// ```
+    pub fn count_byte(&self, b: u8) -> usize {
+        #[cfg(feature = "memchr")]
+        {
+            memchr::memchr_iter(b, &self.0).count()
+        }
+        #[cfg(not(feature = "memchr"))]
+        {
+            self.0.iter().filter(|&&byte| byte == b).count()
+        }
+    }
+
+    /// Finds the byte offset of the first match of `pat`
+    ///
+    /// This is the same as [`str::find`], for the patterns that implement
+    /// [`Pattern`]: a `char`, a string, a slice or array of `char`s to match
+    /// any of, or a closure that picks out the chars to match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("née: 🦀, crab");
+    /// assert_eq!(text.find(':'), Some(4));
+    /// assert_eq!(text.find("crab"), Some(12));
+    /// assert_eq!(text.find(&[',', ':']), Some(4));
+    /// assert_eq!(text.find("lobster"), None);
+    /// assert_eq!(text.find(char::is_whitespace), Some(5));
+    ///
+    /// let rest = text.get(text.find('🦀').unwrap()..).unwrap();
+    /// assert_eq!(rest, "🦀, crab");
+    /// ```
+    pub fn find<P: Pattern>(&self, pat: P) -> Option<usize> {
+        pat.find_in(self)
+    }
+
+    /// Finds the byte offset of the start of the last match of `pat`
+    ///
+    /// This is the same as [`str::rfind`], for the patterns that implement
+    /// [`Pattern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("a/b/ç/d");
+    /// assert_eq!(text.rfind('/'), Some(6));
+    /// assert_eq!(text.rfind("b/"), Some(2));
+    /// assert_eq!(text.rfind(['ç', 'b']), Some(4));
+    /// assert_eq!(text.rfind('x'), None);
+    /// ```
+    pub fn rfind<P: Pattern>(&self, pat: P) -> Option<usize> {
+        pat.rfind_in(self)
+    }
+
+    /// Finds the byte offsets of all non-overlapping matches of `pat`
+    ///
+    /// Matches are found like [`Text::match_indices`], so `pat` can be any
+    /// [`Pattern`], and an empty string matches at every char boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("to be or not to be");
+    /// assert_eq!(text.find_all("be"), [3, 16]);
+    /// assert!(text.find_all("xyz").is_empty());
+    /// assert_eq!(text.find_all('t'), [0, 11, 13]);
+    /// ```
+    pub fn find_all<P: Pattern>(&self, pat: P) -> Vec<usize> {
+        self.match_indices(pat).map(|(i, _)| i).collect()
+    }
+
+    /// An iterator over the non-overlapping matches of `pat` in this text
+    ///
+    /// Matches are found left to right like [`str::matches`], but each one
+    /// is a `Text` that shares this text's buffer. `pat` can be any
+    /// [`Pattern`], such as a string, a `char` or a closure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let log = Text::from("ERROR disk, WARN cpu, ERROR net");
+    /// assert_eq!(log.matches("ERROR").count(), 2);
+    /// assert_eq!(log.matches(',').count(), 2);
+    ///
+    /// let version = Text::from("v1.12.3");
+    /// let digits: Vec<_> = version.matches(char::is_numeric).collect();
+    /// assert_eq!(digits, ["1", "1", "2", "3"]);
+    /// ```
+    pub fn matches<P: Pattern>(&self, pat: P) -> Matches<P> {
+        Matches::new(self.clone(), pat)
+    }
+
+    /// An iterator over the non-overlapping matches of `pat` in this text,
+    /// along with the byte offset each one starts at
+    ///
+    /// Matches are found left to right like [`str::match_indices`], but each
+    /// one is a `Text` that shares this text's buffer. `pat` can be any
+    /// [`Pattern`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("crab 🦀, more 🦀");
+    /// let found: Vec<(usize, Text)> = text.match_indices("🦀").collect();
+    /// assert_eq!(found, [(5, "🦀".into()), (16, "🦀".into())]);
+    /// let found: Vec<(usize, Text)> = text.match_indices(',').collect();
+    /// assert_eq!(found, [(9, ",".into())]);
+    /// ```
+    pub fn match_indices<P: Pattern>(&self, pat: P) -> MatchIndices<P> {
+        MatchIndices::new(self.clone(), pat)
+    }
+
+    /// Replaces all matches of `from` with `to`
+    ///
+    /// Matches are found like [`str::replace`], so `from` can be any
+    /// [`Pattern`], but the result is a `Text` instead of a `String`. If
+    /// there are no matches, this text is returned without copying.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the result would overflow `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let template = Text::from("Hello, {name}! Bye, {name}!");
+    /// assert_eq!(template.replace("{name}", "Ferris"), "Hello, Ferris! Bye, Ferris!");
+    /// assert_eq!(Text::from("a b c").replace(' ', ""), "abc");
+    /// ```
+    pub fn replace<P: Pattern>(&self, from: P, to: &str) -> Text {
+        self.replacen(from, to, usize::MAX)
+    }
+
+    /// Replaces the first `count` matches of `from` with `to`
+    ///
+    /// Matches are found like [`str::replacen`], so `from` can be any
+    /// [`Pattern`], but the result is a `Text` instead of a `String`. If
+    /// there are no matches, or `count` is 0, this text is returned without
+    /// copying.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the result would overflow `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("a-b-c-d");
+    /// assert_eq!(text.replacen("-", " + ", 2), "a + b + c-d");
+    /// assert_eq!(text.replacen(char::is_alphabetic, "x", 1), "x-b-c-d");
+    /// ```
+    pub fn replacen<P: Pattern>(&self, from: P, to: &str, count: usize) -> Text {
+        let matches: Vec<(usize, usize)> = self
+            .match_indices(from)
+            .take(count)
+            .map(|(i, m)| (i, m.len()))
+            .collect();
+        if matches.is_empty() {
+            return self.clone();
+        }
+        // the matches don't overlap, so removing them can't underflow
+        let removed: usize = matches.iter().map(|&(_, len)| len).sum();
+        let len = matches
+            .len()
+            .checked_mul(to.len())
+            .and_then(|added| added.checked_add(self.len() - removed))
+            .expect("capacity overflow");
+        let mut buf = TextMut::with_capacity(len);
+        let mut last = 0;
+        for (i, len) in matches {
+            buf.push_str(&self[last..i]);
+            buf.push_str(to);
+            last = i + len;
+        }
+        buf.push_str(&self[last..]);
+        buf.freeze()
+    }
+
+    /// Counts the occurrences of `pat` in this text, including ones that
+    /// overlap
+    ///
+    /// After each match, the search resumes one char after the start of the
+    /// match, rather than after its end like [`str::matches`] does. For
+    /// example, `"aa"` occurs 3 times in `"aaaa"` here, but `str::matches`
+    /// only finds 2.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::Text;
+    /// let text = Text::from("aaaa");
+    /// assert_eq!(text.count_overlapping("aa"), 3);
+    /// assert_eq!(text.matches("aa").count(), 2);
+    /// ```
+    pub fn count_overlapping(&self, pat: &str) -> usize {
+        let mut count = 0;
+        let mut start = 0;
+        while let Some(i) = self[start..].find(pat) {
+            count += 1;
+            let at = start + i;
+            match self[at..].chars().next() {
+                Some(c) => start = at + c.len_utf8(),
+                None => break,
+            }
+        }
+        count
+    }
+
+    fn as_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(self.0.as_ref()) }
+    }
+
+    /// Counts the UTF-16 code units needed for the UTF-8 `bytes`
+    fn utf16_len_of(bytes: &[u8]) -> usize {
+        // every char is one code unit, except for 4-byte chars, which are
+        // two (a surrogate pair)
+        bytes
+            .iter()
+            .map(|&b| match b {
+                0x80..=0xbf => 0,
+                0xf0..=0xff => 2,
+                _ => 1,
+            })
+            .sum()
+    }
+
+    /// Adds up `lens`, or returns `None` if the total overflows
+    fn total_len(lens: impl IntoIterator<Item = usize>) -> Option<usize> {
+        lens.into_iter()
+            .try_fold(0usize, |total, len| total.checked_add(len))
+    }
+}
+
+/// Resolves `r` to a start and end in `s`, checking that they're in order and
+/// both char boundaries
+pub(crate) fn char_range(s: &str, r: impl RangeBounds<usize>) -> Option<(usize, usize)> {
+    let start = match r.start_bound() {
+        core::ops::Bound::Included(&i) => i,
+        core::ops::Bound::Excluded(&i) => i.checked_add(1)?,
+        core::ops::Bound::Unbounded => 0,
+    };
+    let end = match r.end_bound() {
+        core::ops::Bound::Included(&i) => i.checked_add(1)?,
+        core::ops::Bound::Excluded(&i) => i,
+        core::ops::Bound::Unbounded => s.len(),
+    };
+    // str::is_char_boundary returns false if the index is out of bounds,
+    // so there's no need to check for it here
+    soft_assert::soft_assert!(s.is_char_boundary(start) && s.is_char_boundary(end));
+    soft_assert::soft_assert!(start <= end);
+    Some((start, end))
+}
+
+// ## Conversions
+
+impl AsRef<str> for Text {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for Text {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Text {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Deref for Text {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl From<&'static str> for Text {
+    fn from(s: &'static str) -> Self {
+        Self::from_static(s)
+    }
+}
+
+impl From<String> for Text {
     fn from(s: String) -> Self {
         Self(Bytes::from(s.into_bytes()))
     }
-}
+}
+
+impl From<Cow<'static, str>> for Text {
+    fn from(s: Cow<'static, str>) -> Self {
+        match s {
+            Cow::Borrowed(s) => Self::from_static(s),
+            Cow::Owned(s) => Self::from(s),
+        }
+    }
+}
+
+impl<'a> From<&Text> for Cow<'a, str> {
+    fn from(t: &Text) -> Self {
+        Cow::Owned(t.as_string())
+    }
+}
+
+impl From<Box<str>> for Text {
+    fn from(s: Box<str>) -> Self {
+        Self(Bytes::from(s.into_boxed_bytes()))
+    }
+}
+
+/// Lets `Bytes` keep an `Arc<str>` alive as its owner
+struct ArcStr(Arc<str>);
+
+impl AsRef<[u8]> for ArcStr {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl From<Arc<str>> for Text {
+    /// Shares the `Arc`'s allocation rather than copying it, so the string is
+    /// freed once both the `Arc` and every `Text` made from it are dropped
+    fn from(s: Arc<str>) -> Self {
+        Self(Bytes::from_owner(ArcStr(s)))
+    }
+}
+
+impl TryFrom<Bytes> for Text {
+    type Error = FromUtf8Error;
+
+    fn try_from(b: Bytes) -> Result<Self, Self::Error> {
+        Self::from_utf8(b)
+    }
+}
+
+impl TryFrom<&[u8]> for Text {
+    type Error = Utf8Error;
+
+    fn try_from(b: &[u8]) -> Result<Self, Self::Error> {
+        let _ = core::str::from_utf8(b)?;
+        Ok(Self(Bytes::copy_from_slice(b)))
+    }
+}
+
+impl TryFrom<Vec<u8>> for Text {
+    type Error = FromUtf8Error<Vec<u8>>;
+
+    fn try_from(b: Vec<u8>) -> Result<Self, Self::Error> {
+        match core::str::from_utf8(&b) {
+            Ok(_) => Ok(Self(Bytes::from(b))),
+            Err(e) => Err(FromUtf8Error::new(b, e)),
+        }
+    }
+}
+
+impl From<Text> for Bytes {
+    fn from(t: Text) -> Self {
+        t.into_bytes()
+    }
+}
+
+// ## Formatting
+
+impl Display for Text {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl Debug for Text {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+// ## Comparisons
+
+// ### Self comparisons
+
+impl PartialEq for Text {
+    fn eq(&self, other: &Self) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl Eq for Text {}
+
+impl PartialEq<&Text> for Text {
+    fn eq(&self, other: &&Text) -> bool {
+        (**self).eq(&***other)
+    }
+}
+
+impl PartialEq<&mut Text> for Text {
+    fn eq(&self, other: &&mut Text) -> bool {
+        (**self).eq(&***other)
+    }
+}
+
+impl PartialOrd for Text {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Text {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+// ### str comparisons
+
+impl PartialEq<str> for Text {
+    fn eq(&self, other: &str) -> bool {
+        (**self).eq(other)
+    }
+}
+
+impl PartialEq<&str> for Text {
+    fn eq(&self, other: &&str) -> bool {
+        (**self).eq(*other)
+    }
+}
+
+impl PartialEq<&mut str> for Text {
+    fn eq(&self, other: &&mut str) -> bool {
+        (**self).eq(*other)
+    }
+}
+
+impl PartialOrd<str> for Text {
+    fn partial_cmp(&self, other: &str) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(other)
+    }
+}
+
+impl PartialOrd<&str> for Text {
+    fn partial_cmp(&self, other: &&str) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(*other)
+    }
+}
+
+impl PartialOrd<&mut str> for Text {
+    fn partial_cmp(&self, other: &&mut str) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(*other)
+    }
+}
+
+// ### String comparisons
+
+impl PartialEq<String> for Text {
+    fn eq(&self, other: &String) -> bool {
+        (**self).eq(other)
+    }
+}
+
+impl PartialEq<&String> for Text {
+    fn eq(&self, other: &&String) -> bool {
+        (**self).eq(*other)
+    }
+}
+
+impl PartialEq<&mut String> for Text {
+    fn eq(&self, other: &&mut String) -> bool {
+        (**self).eq(*other)
+    }
+}
+
+impl PartialOrd<String> for Text {
+    fn partial_cmp(&self, other: &String) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl PartialOrd<&String> for Text {
+    fn partial_cmp(&self, other: &&String) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&***other)
+    }
+}
+
+impl PartialOrd<&mut String> for Text {
+    fn partial_cmp(&self, other: &&mut String) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&***other)
+    }
+}
+
+// ### TextMut Comparisons
+
+impl PartialEq<TextMut> for Text {
+    fn eq(&self, other: &TextMut) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl PartialEq<&TextMut> for Text {
+    fn eq(&self, other: &&TextMut) -> bool {
+        (**self).eq(&***other)
+    }
+}
+
+impl PartialEq<&mut TextMut> for Text {
+    fn eq(&self, other: &&mut TextMut) -> bool {
+        (**self).eq(&***other)
+    }
+}
+
+impl PartialOrd<TextMut> for Text {
+    fn partial_cmp(&self, other: &TextMut) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl PartialOrd<&TextMut> for Text {
+    fn partial_cmp(&self, other: &&TextMut) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&***other)
+    }
+}
+
+impl PartialOrd<&mut TextMut> for Text {
+    fn partial_cmp(&self, other: &&mut TextMut) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&***other)
+    }
+}
+
+// ### Box<str>, Rc<str>, and Arc<str> comparisons
+
+impl PartialEq<Box<str>> for Text {
+    fn eq(&self, other: &Box<str>) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl PartialEq<Rc<str>> for Text {
+    fn eq(&self, other: &Rc<str>) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl PartialEq<Arc<str>> for Text {
+    fn eq(&self, other: &Arc<str>) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl PartialOrd<Box<str>> for Text {
+    fn partial_cmp(&self, other: &Box<str>) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl PartialOrd<Rc<str>> for Text {
+    fn partial_cmp(&self, other: &Rc<str>) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl PartialOrd<Arc<str>> for Text {
+    fn partial_cmp(&self, other: &Arc<str>) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+// ## Hash
+
+impl Hash for Text {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+// ## FromIterator
+
+impl FromIterator<char> for Text {
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        iter.into_iter().collect::<TextMut>().freeze()
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Text {
+    fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
+        iter.into_iter().collect::<TextMut>().freeze()
+    }
+}
+
+impl FromIterator<String> for Text {
+    fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+        iter.into_iter().collect::<TextMut>().freeze()
+    }
+}
+
+// ## Serde
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Text {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Text {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TextVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for TextVisitor {
+            type Value = Text;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Text, E> {
+                Ok(Text::copy_from(v))
+            }
+
+            // takes ownership of the buffer instead of copying it
+            fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Text, E> {
+                Ok(Text::from(v))
+            }
+        }
+
+        // asks for an owned `String` if the format has one to give, to avoid
+        // a copy
+        deserializer.deserialize_string(TextVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_char_and_span() {
+        let text = Text::from("ab✓cd");
+        // `✓` is 3 bytes long, starting at byte 2
+        assert_eq!(text.char_and_span(2), Some(('✓', 2..5)));
+        assert_eq!(text.char_and_span(3), Some(('✓', 2..5)));
+        assert_eq!(text.char_and_span(4), Some(('✓', 2..5)));
+        assert_eq!(text.char_and_span(0), Some(('a', 0..1)));
+        assert_eq!(text.char_and_span(6), Some(('d', 6..7)));
+        assert_eq!(text.char_and_span(7), None);
+        assert_eq!(Text::new().char_and_span(0), None);
+    }
+
+    #[test]
+    fn text_cmp_owning_str_pointers() {
+        let text = Text::from("héllo");
+        assert_eq!(text, Box::<str>::from("héllo"));
+        assert_eq!(text, Rc::<str>::from("héllo"));
+        assert_eq!(text, Arc::<str>::from("héllo"));
+        assert_ne!(text, Arc::<str>::from("hello"));
+        let world: Box<str> = "world".into();
+        assert!(text < world);
+        let abc: Rc<str> = "abc".into();
+        assert!(text > abc);
+        let same: Arc<str> = "héllo".into();
+        assert!(text <= same);
+    }
+
+    #[test]
+    fn text_join_iter() {
+        let text = Text::from("α,β,γ");
+        let joined = Text::join_iter(text.split(',').map(Text::copy_from), " | ");
+        assert_eq!(joined, "α | β | γ");
+
+        let single = Text::from("alone");
+        let joined = Text::join_iter(std::iter::once(single.clone()), ", ");
+        assert_eq!(joined, "alone");
+        assert_eq!(joined.as_bytes().as_ptr(), single.as_bytes().as_ptr());
+
+        assert_eq!(Text::join_iter(std::iter::empty(), ", "), "");
+    }
 
-impl TryFrom<Bytes> for Text {
-    type Error = Utf8Error;
+    #[test]
+    fn text_debug_bytes() {
+        let text = Text::from("GET /\\ HTTP/1.1\r\n\0\x7f");
+        assert_eq!(text.debug_bytes(), r"GET /\\ HTTP/1.1\x0d\x0a\x00\x7f");
+        assert!(text.debug_bytes().is_ascii());
+        assert_eq!(Text::from("plain").debug_bytes(), "plain");
+        assert_eq!(Text::new().debug_bytes(), "");
+    }
 
-    fn try_from(b: Bytes) -> Result<Self, Self::Error> {
-        Self::from_utf8(b)
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn text_split_at_grapheme() {
+        // a flag is made of two regional indicator chars
+        let text = Text::from("a🇳🇴b");
+        let (a, b) = text.clone().split_at_grapheme(1).unwrap();
+        assert_eq!(a, "a");
+        assert_eq!(b, "🇳🇴b");
+        let (a, b) = text.clone().split_at_grapheme(2).unwrap();
+        assert_eq!(a, "a🇳🇴");
+        assert_eq!(b, "b");
+
+        // combining acute accent stays with its base char
+        let text = Text::from("cafe\u{301}!");
+        let (a, b) = text.clone().split_at_grapheme(4).unwrap();
+        assert_eq!(a, "cafe\u{301}");
+        assert_eq!(b, "!");
+
+        let (a, b) = text.clone().split_at_grapheme(5).unwrap();
+        assert_eq!(a, "cafe\u{301}!");
+        assert_eq!(b, "");
+        assert_eq!(text.clone().split_at_grapheme(6), Err(text));
     }
-}
 
-impl From<Text> for Bytes {
-    fn from(t: Text) -> Self {
-        t.into_bytes()
+    #[test]
+    fn text_hexdump() {
+        let text = Text::from("GET / HTTP/1.1\r\nHost: é\r\n");
+        let expected = concat!(
+            "00000000: 47 45 54 20 2f 20 48 54 54 50 2f 31 2e 31 0d 0a  GET / HTTP/1.1..\n",
+            "00000010: 48 6f 73 74 3a 20 c3 a9 0d 0a                    Host: ....\n",
+        );
+        assert_eq!(text.hexdump(), expected);
+        assert_eq!(Text::new().hexdump(), "");
     }
-}
 
-// ## Formatting
+    #[test]
+    fn text_count_overlapping() {
+        assert_eq!(Text::from("aaaa").count_overlapping("aa"), 3);
+        assert_eq!(Text::from("aaaa").count_overlapping("b"), 0);
+        assert_eq!(Text::from("ababa").count_overlapping("aba"), 2);
+        assert_eq!(Text::from("€€€x€€").count_overlapping("€€"), 3);
+        assert_eq!(Text::from("ñññ").count_overlapping("ññ"), 2);
+        // same as `str::matches` for an empty pattern
+        assert_eq!(Text::from("añb").count_overlapping(""), 4);
+    }
 
-impl Display for Text {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Display::fmt(self.as_str(), f)
+    #[test]
+    fn text_split_within_bytes() {
+        let text = Text::from("ab🦀cd");
+        // `🦀` is 4 bytes long, starting at byte 2
+        for max_bytes in 2..6 {
+            let (a, b) = text.split_within_bytes(max_bytes);
+            assert_eq!(a, "ab");
+            assert_eq!(b, "🦀cd");
+        }
+        let (a, b) = text.split_within_bytes(6);
+        assert_eq!(a, "ab🦀");
+        assert_eq!(b, "cd");
+        let (a, b) = text.split_within_bytes(100);
+        assert_eq!(a, "ab🦀cd");
+        assert_eq!(b, "");
+        let (a, b) = text.split_within_bytes(0);
+        assert_eq!(a, "");
+        assert_eq!(b, "ab🦀cd");
     }
-}
 
-impl Debug for Text {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Debug::fmt(self.as_str(), f)
+    #[test]
+    #[cfg(feature = "unicode-segmentation")]
+    fn text_grapheme_count() {
+        // thumbs up + skin tone modifier
+        let text = Text::from("👍🏽");
+        assert_eq!(text.grapheme_count(), 1);
+        assert_eq!(text.chars().count(), 2);
+        // family emoji joined with ZWJs
+        let text = Text::from("a👨‍👩‍👧b");
+        assert_eq!(text.grapheme_count(), 3);
+        assert_eq!(text.chars().count(), 7);
+        assert_eq!(Text::from("e\u{301}").grapheme_count(), 1);
+        assert_eq!(Text::new().grapheme_count(), 0);
     }
-}
 
-// ## Comparisons
+    #[test]
+    fn text_strip_prefix_ignore_ascii_case() {
+        let text = Text::from("CONTENT-type: text/plain");
+        assert_eq!(
+            text.strip_prefix_ignore_ascii_case("Content-Type: ")
+                .unwrap(),
+            "text/plain"
+        );
+        assert_eq!(text.strip_prefix_ignore_ascii_case("").unwrap(), text);
+        assert_eq!(
+            text.strip_prefix_ignore_ascii_case("content-TYPE: TEXT/PLAIN")
+                .unwrap(),
+            ""
+        );
+        assert!(text
+            .strip_prefix_ignore_ascii_case("Content-Length")
+            .is_none());
+        assert!(text
+            .strip_prefix_ignore_ascii_case("CONTENT-type: text/plain; more")
+            .is_none());
 
-// ### Self comparisons
+        // non-ASCII chars have to match exactly
+        let text = Text::from("Ñandú");
+        assert_eq!(text.strip_prefix_ignore_ascii_case("Ñan").unwrap(), "dú");
+        assert!(text.strip_prefix_ignore_ascii_case("ñan").is_none());
+    }
 
-impl PartialEq for Text {
-    fn eq(&self, other: &Self) -> bool {
-        (&**self).eq(&**other)
+    #[test]
+    fn text_into_string() {
+        // uniquely owned, so the buffer is reused
+        let string = String::from("unique ✓");
+        let ptr = string.as_ptr();
+        let text = Text::from(string);
+        let string = text.into_string();
+        assert_eq!(string, "unique ✓");
+        assert_eq!(string.as_ptr(), ptr);
+
+        // shared, so it has to be copied
+        let text = Text::from(String::from("shared ✓"));
+        let other = text.clone();
+        let string = text.into_string();
+        assert_eq!(string, "shared ✓");
+        assert_ne!(string.as_ptr(), other.as_ptr());
+        assert_eq!(other, "shared ✓");
+
+        // static, so it has to be copied
+        let text = Text::from_static("static ✓");
+        let ptr = text.as_ptr();
+        let string = text.into_string();
+        assert_eq!(string, "static ✓");
+        assert_ne!(string.as_ptr(), ptr);
+
+        let text = Text::from("copied ✓");
+        let string = text.as_string();
+        assert_eq!(text, string);
+        assert_ne!(string.as_ptr(), text.as_ptr());
+        assert_eq!(text.to_string(), text.as_string());
     }
-}
 
-impl Eq for Text {}
+    #[test]
+    fn text_chars_in_first_bytes() {
+        let text = Text::from("a🦀b");
+        // `🦀` is 4 bytes long, starting at byte 1
+        assert_eq!(text.chars_in_first_bytes(0), 0);
+        assert_eq!(text.chars_in_first_bytes(1), 1);
+        for max_bytes in 2..5 {
+            assert_eq!(text.chars_in_first_bytes(max_bytes), 1);
+        }
+        assert_eq!(text.chars_in_first_bytes(5), 2);
+        assert_eq!(text.chars_in_first_bytes(6), 3);
+        assert_eq!(text.chars_in_first_bytes(usize::MAX), 3);
+    }
 
-impl PartialEq<&Text> for Text {
-    fn eq(&self, other: &&Text) -> bool {
-        (&**self).eq(&***other)
+    #[test]
+    fn text_common_prefix_of() {
+        assert_eq!(Text::common_prefix_of(&[]), "");
+
+        let single = Text::from("alone");
+        let prefix = Text::common_prefix_of(std::slice::from_ref(&single));
+        assert_eq!(prefix, "alone");
+        assert_eq!(prefix.as_ptr(), single.as_ptr());
+
+        let texts = [
+            Text::from("日本語のテキスト"),
+            Text::from("日本語の文"),
+            Text::from("日本語の本です"),
+        ];
+        let prefix = Text::common_prefix_of(&texts);
+        assert_eq!(prefix, "日本語の");
+        assert_eq!(prefix.as_ptr(), texts[0].as_ptr());
+
+        // 'é' and 'ê' share their first byte, which can't be included
+        let texts = [Text::from("caé"), Text::from("caê")];
+        assert_eq!(Text::common_prefix_of(&texts), "ca");
+
+        let texts = [Text::from("abc"), Text::from("xyz")];
+        assert_eq!(Text::common_prefix_of(&texts), "");
     }
-}
 
-impl PartialEq<&mut Text> for Text {
-    fn eq(&self, other: &&mut Text) -> bool {
-        (&**self).eq(&***other)
+    #[test]
+    fn text_from_utf8_diagnostic() {
+        let text = Text::from_utf8_diagnostic(Bytes::from_static("fine ✓".as_bytes())).unwrap();
+        assert_eq!(text, "fine ✓");
+
+        let buf = Bytes::from_static(b"GET /caf\xe9 HTTP/1.1");
+        let err = Text::from_utf8_diagnostic(buf).unwrap_err();
+        assert_eq!(err.valid_up_to(), 8);
+        assert_eq!(err.invalid_bytes(), b"\xe9");
+        assert_eq!(err.snippet(), "2f 63 61 66 [e9] 20 48 54 54");
+        assert_eq!(err.utf8_error().error_len(), Some(1));
+
+        // error right at the start, with little context
+        let err = Text::from_utf8_diagnostic(Bytes::from_static(b"\x80ab")).unwrap_err();
+        assert_eq!(err.valid_up_to(), 0);
+        assert_eq!(err.snippet(), "[80] 61 62");
+
+        // input ends partway through a 4-byte sequence
+        let err = Text::from_utf8_diagnostic(Bytes::from_static(b"ok\xf0\x9f\xa6")).unwrap_err();
+        assert_eq!(err.valid_up_to(), 2);
+        assert_eq!(err.invalid_bytes(), b"\xf0\x9f\xa6");
+        assert_eq!(err.snippet(), "6f 6b [f0 9f a6]");
     }
-}
 
-impl PartialOrd for Text {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+    #[test]
+    fn text_char_indices_in() {
+        let text = Text::from("ñ🦀xyz");
+        let indices: Vec<(usize, char)> = text.char_indices_in(2..7).unwrap().collect();
+        assert_eq!(indices, [(2, '🦀'), (6, 'x')]);
+        let indices: Vec<(usize, char)> = text.char_indices_in(..=6).unwrap().collect();
+        assert_eq!(indices, [(0, 'ñ'), (2, '🦀'), (6, 'x')]);
+        // same as `str::char_indices` on the whole range
+        assert!(text.char_indices_in(..).unwrap().eq(text.char_indices()));
+        assert_eq!(text.char_indices_in(9..9).unwrap().count(), 0);
+
+        assert!(text.char_indices_in(1..).is_none());
+        assert!(text.char_indices_in(..3).is_none());
+        assert!(text.char_indices_in(..10).is_none());
+        let (start, end) = (6, 2);
+        assert!(text.char_indices_in(start..end).is_none());
+        assert!(text.get(start..end).is_none());
     }
-}
 
-impl Ord for Text {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        (&**self).cmp(&**other)
+    #[test]
+    fn text_compact() {
+        let huge = Text::from("🦀".repeat(1024) + "small");
+        let slice = huge.get(4096..).unwrap();
+        assert_eq!(slice.as_ptr(), huge[4096..].as_ptr());
+
+        let compacted = slice.compact();
+        assert_eq!(compacted, "small");
+        assert_ne!(compacted.as_ptr(), slice.as_ptr());
+        // the compacted text is the only handle to its new buffer
+        assert!(compacted.as_bytes().is_unique());
+
+        drop(slice);
+        // nothing else refers to the big buffer now
+        assert!(huge.as_bytes().is_unique());
+
+        assert_eq!(huge.get(4..4).unwrap().compact(), "");
     }
-}
-// ### str comparisons
 
-impl PartialEq<str> for Text {
-    fn eq(&self, other: &str) -> bool {
-        (&**self).eq(other)
+    #[test]
+    fn text_eq_ignore_ascii_case_bytes() {
+        let text = Text::from("Content-Type");
+        assert!(text.eq_ignore_ascii_case_bytes(b"content-type"));
+        assert!(text.eq_ignore_ascii_case_bytes(b"CONTENT-TYPE"));
+        assert!(!text.eq_ignore_ascii_case_bytes(b"content-type "));
+        assert!(!text.eq_ignore_ascii_case_bytes(b"content_type"));
+        assert!(!text.eq_ignore_ascii_case_bytes(b"\xffontent-type"));
+
+        // non-ASCII bytes have to match exactly
+        let text = Text::from("Ésta");
+        assert!(text.eq_ignore_ascii_case_bytes("ÉSTA".as_bytes()));
+        assert!(!text.eq_ignore_ascii_case_bytes("ésta".as_bytes()));
+        assert!(Text::new().eq_ignore_ascii_case_bytes(b""));
     }
-}
 
-impl PartialEq<&str> for Text {
-    fn eq(&self, other: &&str) -> bool {
-        (&**self).eq(*other)
+    #[test]
+    fn text_to_bytes_mut() {
+        let text = Text::from("héllo");
+        let mut bytes = text.to_bytes_mut();
+        assert_eq!(bytes, "héllo".as_bytes());
+        assert_ne!(bytes.as_ptr(), text.as_ptr());
+
+        bytes[0] = b'j';
+        bytes.extend_from_slice(b"!");
+        assert_eq!(bytes, "jéllo!".as_bytes());
+        assert_eq!(text, "héllo");
     }
-}
 
-impl PartialEq<&mut str> for Text {
-    fn eq(&self, other: &&mut str) -> bool {
-        (&**self).eq(*other)
+    #[test]
+    fn text_into_chars_text() {
+        let text = Text::from("a¢€𐍈");
+        let chars = text.clone().into_chars_text();
+        assert_eq!(chars, ["a", "¢", "€", "𐍈"]);
+        for (i, c) in chars.iter().enumerate() {
+            assert_eq!(c.len(), i + 1);
+        }
+        let joined: String = chars.iter().map(|c| &**c).collect();
+        assert_eq!(text, joined);
+        assert!(Text::new().into_chars_text().is_empty());
     }
-}
 
-impl PartialOrd<str> for Text {
-    fn partial_cmp(&self, other: &str) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(other)
+    #[test]
+    fn text_split_at_many() {
+        let text = Text::from("🦀ab€cd");
+        let parts = text.split_at_many(&[1, 4]).unwrap();
+        assert_eq!(parts, ["🦀", "ab€", "cd"]);
+        assert_eq!(parts[1].as_ptr(), text[4..].as_ptr());
+
+        let parts = text.split_at_many(&[0, 2, 2, 6]).unwrap();
+        assert_eq!(parts, ["", "🦀a", "", "b€cd", ""]);
+        assert_eq!(text.split_at_many(&[]).unwrap(), ["🦀ab€cd"]);
+
+        assert!(text.split_at_many(&[7]).is_none());
+        assert!(text.split_at_many(&[3, 1]).is_none());
+        assert_eq!(Text::new().split_at_many(&[0]).unwrap(), ["", ""]);
     }
-}
 
-impl PartialOrd<&str> for Text {
-    fn partial_cmp(&self, other: &&str) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(*other)
+    #[test]
+    fn text_split_once_slice() {
+        let text = Text::from("key=välue=more");
+        let (a, b) = text.split_once_slice(b"=").unwrap();
+        assert_eq!(a, "key");
+        assert_eq!(b, "välue=more");
+        assert_eq!(b.as_ptr(), text[4..].as_ptr());
+
+        let (a, b) = text.split_once_slice("ä".as_bytes()).unwrap();
+        assert_eq!(a, "key=v");
+        assert_eq!(b, "lue=more");
+
+        let (a, b) = text.split_once_slice(b"").unwrap();
+        assert_eq!(a, "");
+        assert_eq!(b, text);
+
+        assert!(text.split_once_slice(b"==").is_none());
+        assert!(text.split_once_slice(b"key=value=more").is_none());
+        // only part of `ä`, so the split would land inside it
+        assert!(text.split_once_slice(b"\xa4").is_none());
+        assert!(text.split_once_slice(b"v\xc3").is_none());
     }
-}
 
-impl PartialOrd<&mut str> for Text {
-    fn partial_cmp(&self, other: &&mut str) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(*other)
+    #[test]
+    fn text_filter_lines() {
+        let text = Text::from("one\n\n  \ntwo\r\n\nthree");
+        let filtered = text.filter_lines(|line| !line.trim().is_empty());
+        assert_eq!(filtered, "one\ntwo\r\nthree");
+
+        let config = Text::from("# comment\nkey = value\n  # indented\nother = 1\n");
+        let filtered = config.filter_lines(|line| !line.trim_start().starts_with('#'));
+        assert_eq!(filtered, "key = value\nother = 1\n");
+
+        let kept = config.filter_lines(|_| true);
+        assert_eq!(kept, config);
+        assert_eq!(kept.as_ptr(), config.as_ptr());
+
+        assert_eq!(config.filter_lines(|_| false), "");
+        assert_eq!(Text::new().filter_lines(|_| false), "");
+
+        // the lines before the first dropped one are kept as they were
+        let filtered = config.filter_lines(|line| line != "other = 1");
+        assert_eq!(filtered, "# comment\nkey = value\n  # indented\n");
+        let filtered = text.filter_lines(|line| !line.is_empty());
+        assert_eq!(filtered, "one\n  \ntwo\r\nthree");
+        let filtered = text.filter_lines(|line| line != "one");
+        assert_eq!(filtered, "\n  \ntwo\r\n\nthree");
     }
-}
 
-// ### String comparisons
+    #[test]
+    fn text_char_boundaries() {
+        let text = Text::from("héllo, wörld 🌍!");
+        let boundaries: Vec<usize> = text.char_boundaries().collect();
+        assert_eq!(boundaries.len(), text.chars().count() + 1);
+        assert_eq!(boundaries.first(), Some(&0));
+        assert_eq!(boundaries.last(), Some(&text.len()));
+        assert!(boundaries.iter().all(|&i| text.is_char_boundary(i)));
+        assert!(boundaries.windows(2).all(|w| w[0] < w[1]));
 
-impl PartialEq<String> for Text {
-    fn eq(&self, other: &String) -> bool {
-        (&**self).eq(other)
+        assert!(Text::new().char_boundaries().eq([0]));
     }
-}
 
-impl PartialEq<&String> for Text {
-    fn eq(&self, other: &&String) -> bool {
-        (&**self).eq(*other)
+    #[test]
+    fn text_first_last_char() {
+        let empty = Text::new();
+        assert_eq!(empty.first_char(), None);
+        assert_eq!(empty.last_char(), None);
+
+        let single = Text::from("€");
+        assert_eq!(single.first_char(), Some('€'));
+        assert_eq!(single.last_char(), Some('€'));
+
+        let multi = Text::from("🌍 hello ñ");
+        assert_eq!(multi.first_char(), Some('🌍'));
+        assert_eq!(multi.last_char(), Some('ñ'));
     }
-}
 
-impl PartialEq<&mut String> for Text {
-    fn eq(&self, other: &&mut String) -> bool {
-        (&**self).eq(*other)
+    #[test]
+    fn text_starts_with_bytes() {
+        let text = Text::from("GIF89a...");
+        assert!(text.starts_with_bytes(b"GIF8"));
+        assert!(text.starts_with_bytes(b""));
+        assert!(!text.starts_with_bytes(b"\x89PNG"));
+        assert!(!text.starts_with_bytes(b"GIF89a...and more"));
+        assert!(!Text::new().starts_with_bytes(b"G"));
     }
-}
 
-impl PartialOrd<String> for Text {
-    fn partial_cmp(&self, other: &String) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&**other)
+    #[test]
+    fn text_split_enumerate() {
+        let text = Text::from("a,bé,,c");
+        let fields: Vec<(usize, Text)> = text.split_enumerate(',').collect();
+        assert_eq!(
+            fields,
+            [
+                (0, Text::from("a")),
+                (1, Text::from("bé")),
+                (2, Text::from("")),
+                (3, Text::from("c")),
+            ]
+        );
+        assert_eq!(fields[1].1.as_ptr(), text[2..].as_ptr());
+
+        assert!(Text::new().split_enumerate(',').eq([(0, Text::new())]));
     }
-}
 
-impl PartialOrd<&String> for Text {
-    fn partial_cmp(&self, other: &&String) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&***other)
+    #[test]
+    fn text_nth_line() {
+        let text = Text::from("zero\none\r\n\nthree\nfour\n");
+        assert_eq!(text.nth_line(0).unwrap(), "zero");
+        assert_eq!(text.nth_line(1).unwrap(), "one");
+        assert_eq!(text.nth_line(2).unwrap(), "");
+        assert_eq!(text.nth_line(4).unwrap(), "four");
+        assert_eq!(text.nth_line(3).unwrap().as_ptr(), text[11..].as_ptr());
+        // the trailing line ending doesn't start another line
+        assert!(text.nth_line(5).is_none());
+        assert!(text.nth_line(usize::MAX).is_none());
+
+        assert!(Text::new().nth_line(0).is_none());
     }
-}
 
-impl PartialOrd<&mut String> for Text {
-    fn partial_cmp(&self, other: &&mut String) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&***other)
+    #[cfg(feature = "unicode-case")]
+    #[test]
+    fn text_eq_ignore_case() {
+        let text = Text::from("Grüße, Welt");
+        assert!(text.eq_ignore_case("GRÜßE, WELT"));
+        assert!(text.eq_ignore_case("grüße, welt"));
+        // simple folding leaves `ß` alone rather than expanding it to `ss`
+        assert!(!text.eq_ignore_case("GRÜSSE, WELT"));
+        assert!(!Text::from("ß").eq_ignore_case("ss"));
+        // but its capital form still folds to it
+        assert!(Text::from("ß").eq_ignore_case("ẞ"));
+
+        // final and non-final sigma both fold to `σ`
+        let text = Text::from("ΟΔΥΣΣΕΥΣ");
+        assert!(text.eq_ignore_case("οδυσσευς"));
+        assert!(text.eq_ignore_case("οδυσσευσ"));
+        assert!(Text::from("ς").eq_ignore_case("σ"));
+
+        assert!(!text.eq_ignore_case("οδυσσευ"));
+        assert!(Text::new().eq_ignore_case(""));
     }
-}
 
-// ### TextMut Comparisons
+    #[test]
+    fn text_new_const() {
+        const EMPTY: Text = Text::new();
+        static ALSO_EMPTY: Text = Text::new();
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY, ALSO_EMPTY);
+        assert_eq!(EMPTY, Text::default());
+    }
 
-impl PartialEq<TextMut> for Text {
-    fn eq(&self, other: &TextMut) -> bool {
-        (&**self).eq(&**other)
+    #[test]
+    fn text_content_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::Hasher;
+
+        fn digest(text: &Text) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            text.content_hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a = Text::from("content ✓");
+        let b = Text::copy_from("content ✓");
+        assert_ne!(a.as_ptr(), b.as_ptr());
+        assert_eq!(digest(&a), digest(&b));
+        assert_ne!(digest(&a), digest(&Text::from("content ✗")));
+
+        // a slice hashes the same as a fresh copy of its contents
+        let slice = Text::from("xx content ✓ xx").get(3..14).unwrap();
+        assert_eq!(digest(&slice), digest(&a));
     }
-}
 
-impl PartialEq<&TextMut> for Text {
-    fn eq(&self, other: &&TextMut) -> bool {
-        (&**self).eq(&***other)
+    #[test]
+    fn text_try_concat() {
+        let parts = [Text::from("añ"), Text::new(), Text::from("o ✓")];
+        assert_eq!(Text::try_concat(&parts).unwrap(), "año ✓");
+        assert_eq!(Text::try_concat(&[]).unwrap(), "");
+
+        let single = Text::from("single");
+        let concatenated = Text::try_concat(std::slice::from_ref(&single)).unwrap();
+        assert_eq!(concatenated.as_ptr(), single.as_ptr());
+
+        // real texts can't be big enough to overflow, so check the length
+        // calculation directly
+        assert_eq!(Text::total_len([1, 2, 3]), Some(6));
+        assert_eq!(Text::total_len([usize::MAX, 0]), Some(usize::MAX));
+        assert_eq!(Text::total_len([usize::MAX, 1]), None);
+        assert_eq!(Text::total_len([usize::MAX / 2 + 1; 2]), None);
     }
-}
 
-impl PartialEq<&mut TextMut> for Text {
-    fn eq(&self, other: &&mut TextMut) -> bool {
-        (&**self).eq(&***other)
+    #[cfg(feature = "serde")]
+    #[test]
+    fn text_serde() {
+        for s in ["", "héllo, wörld 🌍", r#"say "hi" \ bye"#, "line\nbreak"] {
+            let text = Text::from(s);
+            let json = serde_json::to_string(&text).unwrap();
+            assert_eq!(json, serde_json::to_string(s).unwrap());
+            let back: Text = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, s);
+
+            // owned strings are moved in, through `visit_string`
+            let back: Text = serde_json::from_value(serde_json::Value::from(s)).unwrap();
+            assert_eq!(back, s);
+        }
+
+        assert!(serde_json::from_str::<Text>("42").is_err());
     }
-}
 
-impl PartialOrd<TextMut> for Text {
-    fn partial_cmp(&self, other: &TextMut) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&**other)
+    #[test]
+    fn text_slice_ref() {
+        let text = Text::from("key: välue ");
+        let value = text.slice_ref(text[4..].trim());
+        assert_eq!(value, "välue");
+        assert_eq!(value.as_ptr(), text[5..].as_ptr());
+
+        assert_eq!(text.slice_ref(&text[..0]), "");
+        assert_eq!(text.slice_ref(&text[text.len()..]), "");
+        assert_eq!(text.slice_ref(&text), text);
+
+        // from a text that's already a slice
+        let sub = text.get(5..).unwrap();
+        let word = sub.slice_ref(str::split_whitespace(&sub).next().unwrap());
+        assert_eq!(word, "välue");
+        assert_eq!(word.as_ptr(), value.as_ptr());
     }
-}
 
-impl PartialOrd<&TextMut> for Text {
-    fn partial_cmp(&self, other: &&TextMut) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&***other)
+    #[test]
+    #[should_panic]
+    fn text_slice_ref_outside() {
+        let text = Text::from("inside");
+        let other = String::from("outside");
+        text.slice_ref(&other);
     }
-}
 
-impl PartialOrd<&mut TextMut> for Text {
-    fn partial_cmp(&self, other: &&mut TextMut) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&***other)
+    #[test]
+    fn text_utf16() {
+        let text = Text::from("x😀é€");
+        let units: Vec<u16> = text.encode_utf16().collect();
+        assert_eq!(units, [0x78, 0xd83d, 0xde00, 0xe9, 0x20ac]);
+        assert_eq!(String::from_utf16(&units).unwrap(), "x😀é€");
+        assert_eq!(text.utf16_len(), 5);
+        assert_eq!(text.chars().count(), 4);
+
+        let emoji = Text::from("😀");
+        assert_eq!(emoji.len(), 4);
+        assert_eq!(emoji.utf16_len(), 2);
+
+        for s in ["", "ascii", "ñandú", "🌍🌎🌏", "a\u{10ffff}\u{ffff}"] {
+            assert_eq!(Text::from(s).utf16_len(), s.encode_utf16().count());
+        }
     }
-}
 
-// ## Hash
+    #[test]
+    fn text_utf16_offsets() {
+        // bytes:  a=0 😀=1..5 b=5 é=6..8 end=8
+        // utf-16: a=0 😀=1..3 b=3 é=4    end=5
+        let text = Text::from("a😀bé");
+        let pairs = [(0, 0), (1, 1), (3, 5), (4, 6), (5, 8)];
+        for (utf16, byte) in pairs {
+            assert_eq!(text.utf16_to_byte(utf16), Some(byte));
+            assert_eq!(text.byte_to_utf16(byte), Some(utf16));
+        }
 
-impl Hash for Text {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        (&**self).hash(state);
+        // between the halves of the surrogate pair
+        assert_eq!(text.utf16_to_byte(2), None);
+        // inside the emoji's bytes
+        for byte in 2..5 {
+            assert_eq!(text.byte_to_utf16(byte), None);
+        }
+        assert_eq!(text.byte_to_utf16(7), None);
+
+        // out of range
+        assert_eq!(text.utf16_to_byte(6), None);
+        assert_eq!(text.byte_to_utf16(9), None);
+
+        let empty = Text::new();
+        assert_eq!(empty.utf16_to_byte(0), Some(0));
+        assert_eq!(empty.byte_to_utf16(0), Some(0));
+        assert_eq!(empty.utf16_to_byte(1), None);
+    }
+
+    #[test]
+    fn text_split_whitespace_indices() {
+        let text = Text::from(" \t fn  main()\u{3000}{\n}  ");
+        let tokens: Vec<(usize, Text)> = text.split_whitespace_indices().collect();
+        assert_eq!(
+            tokens,
+            [
+                (3, "fn".into()),
+                (7, "main()".into()),
+                (16, "{".into()),
+                (18, "}".into()),
+            ]
+        );
+        for (i, token) in &tokens {
+            assert_eq!(token, &text[*i..*i + token.len()]);
+            assert_eq!(token.as_ptr(), text[*i..].as_ptr());
+        }
+
+        assert_eq!(Text::from(" \n\t ").split_whitespace_indices().count(), 0);
+        assert_eq!(Text::new().split_whitespace_indices().count(), 0);
+    }
+
+    #[test]
+    fn text_trim() {
+        let text = Text::from("\u{3000} ünïcode\u{a0}spaces \u{2003}\n");
+        assert_eq!(text.trim(), "ünïcode\u{a0}spaces");
+        assert_eq!(text.trim_start(), "ünïcode\u{a0}spaces \u{2003}\n");
+        assert_eq!(text.trim_end(), "\u{3000} ünïcode\u{a0}spaces");
+        assert_eq!(text.trim().as_ptr(), text[4..].as_ptr());
+
+        // already trimmed, so nothing changes
+        let trimmed = Text::from("already");
+        assert_eq!(trimmed.trim().as_ptr(), trimmed.as_ptr());
+        assert_eq!(trimmed.trim().len(), trimmed.len());
+        assert_eq!(trimmed.trim_start().len(), trimmed.len());
+        assert_eq!(trimmed.trim_end().len(), trimmed.len());
+
+        let blank = Text::from(" \t\r\n ");
+        assert_eq!(blank.trim(), "");
+        assert_eq!(blank.trim_start(), "");
+        assert_eq!(blank.trim_end(), "");
+        assert_eq!(Text::new().trim(), "");
+    }
+
+    #[test]
+    fn text_count_byte() {
+        let line = "ünïcode, and\r\nmore\n";
+        let text = Text::from(line.repeat(1000));
+        for b in [b'\n', b'\r', b',', b'e', 0xc3, 0xbc, b'z'] {
+            let naive = text.as_bytes().iter().filter(|&&x| x == b).count();
+            assert_eq!(text.count_byte(b), naive);
+        }
+        assert_eq!(text.count_byte(b'\n'), 2000);
+        assert_eq!(Text::new().count_byte(b'\n'), 0);
+    }
+
+    #[test]
+    fn text_borrow_str() {
+        use std::collections::{BTreeMap, HashMap};
+
+        let mut map = HashMap::new();
+        map.insert(Text::from("one"), 1);
+        map.insert(Text::copy_from("twö"), 2);
+        assert_eq!(map.get("one"), Some(&1));
+        assert_eq!(map.get("twö"), Some(&2));
+        assert_eq!(map.get("three"), None);
+
+        let mut map = BTreeMap::new();
+        map.insert(Text::from("b"), 2);
+        map.insert(Text::from("a"), 1);
+        assert_eq!(map.get("a"), Some(&1));
+        assert!(map.contains_key("b"));
+    }
+
+    #[test]
+    fn text_as_ref_bytes() {
+        fn byte_len(bytes: impl AsRef<[u8]>) -> usize {
+            bytes.as_ref().len()
+        }
+
+        let text = Text::from("ñ");
+        assert_eq!(byte_len(&text), 2);
+        assert_eq!(byte_len(TextMut::copy_from("ñ")), 2);
+        let bytes: &[u8] = text.as_ref();
+        assert_eq!(bytes, [0xc3, 0xb1]);
+    }
+
+    #[test]
+    fn text_line_start_end() {
+        // lines are 0..5, 6..13, and 14..19
+        let text = Text::from("first\nsécond\nthird");
+        let line = |i| (text.line_start(i).unwrap(), text.line_end(i).unwrap());
+
+        assert_eq!(line(0), (0, 5));
+        assert_eq!(line(3), (0, 5));
+        // the `\n` belongs to the line before it
+        assert_eq!(line(5), (0, 5));
+        assert_eq!(line(6), (6, 13));
+        assert_eq!(line(9), (6, 13));
+        assert_eq!(line(13), (6, 13));
+        assert_eq!(line(14), (14, 19));
+        assert_eq!(line(19), (14, 19));
+        assert_eq!(&text[14..19], "third");
+
+        // inside `é`, and out of range
+        assert_eq!(text.line_start(8), None);
+        assert_eq!(text.line_end(8), None);
+        assert_eq!(text.line_start(20), None);
+        assert_eq!(text.line_end(20), None);
+
+        let trailing = Text::from("a\n");
+        assert_eq!(trailing.line_start(2), Some(2));
+        assert_eq!(trailing.line_end(2), Some(2));
+    }
+
+    #[test]
+    fn text_split_halves() {
+        let text = Text::from("añb");
+
+        let (start, end) = text.clone().split_at(3).unwrap();
+        assert_eq!((start, end), ("añ".into(), "b".into()));
+
+        let (start, end) = text.clone().split_at_checked(3).unwrap();
+        assert_eq!((start, end), ("añ".into(), "b".into()));
+
+        // `split_off` keeps the start, and returns the end
+        let mut kept = text.clone();
+        let returned = kept.split_off(3).unwrap();
+        assert_eq!((kept, returned), ("añ".into(), "b".into()));
+
+        // `split_to` keeps the end, and returns the start
+        let mut kept = text.clone();
+        let returned = kept.split_to(3).unwrap();
+        assert_eq!((kept, returned), ("b".into(), "añ".into()));
+
+        // failures
+        assert_eq!(text.clone().split_at(2).unwrap_err(), text);
+        assert!(text.clone().split_at_checked(2).is_none());
+        assert!(text.clone().split_at_checked(5).is_none());
+        let mut kept = text.clone();
+        assert!(kept.split_off(2).is_none());
+        assert!(kept.split_to(2).is_none());
+        assert_eq!(kept, text);
+    }
+
+    #[test]
+    fn text_cow_bytes() {
+        let text = Text::from("bytés");
+        let borrowed = text.as_cow_bytes();
+        assert!(matches!(borrowed, Cow::Borrowed(_)));
+        assert_eq!(borrowed.as_ptr(), text.as_ptr());
+        assert_eq!(&*borrowed, "bytés".as_bytes());
+
+        // unique, so the buffer is reused
+        let text = Text::from(String::from("owned"));
+        let ptr = text.as_ptr();
+        let owned = text.into_cow_bytes();
+        assert!(matches!(owned, Cow::Owned(_)));
+        assert_eq!(owned.as_ptr(), ptr);
+        assert_eq!(&*owned, b"owned");
+    }
+
+    #[test]
+    fn text_truncate() {
+        let mut text = Text::from("a€b");
+        assert!(text.truncate(10));
+        assert!(text.truncate(5));
+        assert_eq!(text, "a€b");
+
+        // inside `€`
+        assert!(!text.truncate(2));
+        assert!(!text.truncate(3));
+        assert_eq!(text, "a€b");
+
+        assert!(text.truncate(4));
+        assert_eq!(text, "a€");
+        assert!(text.truncate(0));
+        assert_eq!(text, "");
+    }
+
+    #[test]
+    fn text_from_iter() {
+        let reversed: Text = "hello".chars().rev().collect();
+        assert_eq!(reversed, "olleh");
+
+        let joined: Text = vec!["añ", "", "o"].into_iter().collect();
+        assert_eq!(joined, "año");
+
+        let joined: Text = (1..=3).map(|i| i.to_string()).collect();
+        assert_eq!(joined, "123");
+
+        let empty: Text = std::iter::empty::<char>().collect();
+        assert_eq!(empty, "");
+    }
+
+    #[test]
+    fn text_repeat_join() {
+        let text = Text::from("ab");
+        assert_eq!(text.repeat_join(0, "-"), "");
+        let once = text.repeat_join(1, "-");
+        assert_eq!(once, "ab");
+        assert_eq!(once.as_ptr(), text.as_ptr());
+
+        let ruler = Text::from("----").repeat_join(3, "┼");
+        assert_eq!(ruler, "----┼----┼----");
+        assert_eq!(ruler.len(), 4 * 3 + 3 * 2);
+
+        assert_eq!(Text::from("é").repeat_join(4, ""), "éééé");
+        assert_eq!(Text::new().repeat_join(3, ", "), ", , ");
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn text_repeat_join_overflow() {
+        Text::from("ab").repeat_join(usize::MAX / 2 + 1, "");
+    }
+
+    #[test]
+    fn text_find_all() {
+        let text = Text::from("añaña ñaña");
+        assert_eq!(text.find_all("ña"), [1, 4, 8, 11]);
+        assert_eq!(text.find_all("a"), [0, 3, 6, 10, 13]);
+        // non-overlapping
+        assert_eq!(Text::from("aaaa").find_all("aa"), [0, 2]);
+        assert!(text.find_all("b").is_empty());
+        assert!(text.find_all("añaña ñaña!").is_empty());
+
+        // an empty pattern matches at every char boundary
+        assert_eq!(Text::from("añ").find_all(""), [0, 1, 3]);
+        assert_eq!(Text::new().find_all(""), [0]);
+
+        // other patterns, like `Text::find`
+        assert_eq!(text.find_all('ñ'), [1, 4, 8, 11]);
+        assert_eq!(text.find_all(',').first(), text.find(',').as_ref());
+        assert_eq!(text.find_all(char::is_whitespace), [7]);
+        assert_eq!(text.find_all(['a', ' ']), [0, 3, 6, 7, 10, 13]);
+        assert_eq!(text.find_all(|c: char| !c.is_ascii()), [1, 4, 8, 11]);
+    }
+
+    #[test]
+    fn text_from_utf8_lossy() {
+        let valid = Bytes::from(String::from("valid ✓"));
+        let ptr = valid.as_ptr();
+        let text = Text::from_utf8_lossy(valid);
+        assert_eq!(text, "valid ✓");
+        assert_eq!(text.as_ptr(), ptr);
+
+        let invalid = Bytes::from_static(b"bad \xff byte, cut off \xe2\x9c");
+        let text = Text::from_utf8_lossy(invalid.clone());
+        assert_eq!(text, "bad \u{fffd} byte, cut off \u{fffd}");
+        assert_ne!(text.as_ptr(), invalid.as_ptr());
+
+        assert_eq!(Text::from_utf8_lossy(Bytes::new()), "");
+    }
+
+    #[test]
+    fn text_to_lowercase_uppercase() {
+        let cases = [
+            "Straße",
+            "ΟΔΥΣΣΕΥΣ",
+            "ὈΔΥΣΣΕΎΣ καὶ ΣΑ",
+            // no Turkish tailoring, so `I` is always `i`
+            "DIŞ İstanbul Iı",
+            "",
+        ];
+        for s in cases {
+            let text = Text::from(s);
+            assert_eq!(text.to_lowercase(), str::to_lowercase(s));
+            assert_eq!(text.to_uppercase(), str::to_uppercase(s));
+        }
+
+        assert_eq!(Text::from("ß").to_uppercase(), "SS");
+        assert_eq!(Text::from("ΣΑΣ").to_lowercase(), "σας");
+        assert_eq!(Text::from("TITLE").to_lowercase(), "title");
+
+        let mut lower = Text::from("ABC").to_lowercase();
+        lower.push_str("def");
+        assert_eq!(lower.freeze(), "abcdef");
+    }
+
+    #[test]
+    fn text_eq_ignore_ascii_case() {
+        let text = Text::from("X-Ünïcode-Header");
+        assert!(text.eq_ignore_ascii_case("x-Ünïcode-HEADER"));
+        assert!(!text.eq_ignore_ascii_case("x-üNÏCODE-header"));
+        assert!(!text.eq_ignore_ascii_case("X-Ünïcode"));
+    }
+
+    #[test]
+    fn text_strip_prefix_suffix() {
+        let text = Text::from("key: välue");
+
+        let rest = text.strip_prefix("key: ").unwrap();
+        assert_eq!(rest, "välue");
+        assert_eq!(rest.as_ptr(), text[5..].as_ptr());
+        let rest = text.strip_suffix(&String::from("lue")).unwrap();
+        assert_eq!(rest, "key: vä");
+        assert_eq!(rest.as_ptr(), text.as_ptr());
+
+        assert!(text.strip_prefix("value").is_none());
+        assert!(text.strip_suffix("key").is_none());
+        assert!(text.strip_prefix("key: välue!").is_none());
+
+        assert_eq!(text.strip_prefix("key: välue").unwrap(), "");
+        assert_eq!(text.strip_suffix("key: välue").unwrap(), "");
+
+        let whole = text.strip_prefix("").unwrap();
+        assert_eq!(whole, text);
+        assert_eq!(whole.as_ptr(), text.as_ptr());
+        assert_eq!(text.strip_suffix("").unwrap(), text);
+
+        // chars and other patterns, like `str::strip_prefix`
+        let path = Text::from("/srv/data/");
+        assert_eq!(path.strip_prefix('/').unwrap(), "srv/data/");
+        assert_eq!(path.strip_suffix('/').unwrap(), "/srv/data");
+        assert!(path.strip_prefix('s').is_none());
+        assert_eq!(text.strip_suffix('e').unwrap(), "key: välu");
+        assert_eq!(text.strip_prefix(['k', 'v']).unwrap(), "ey: välue");
+        assert_eq!(text.strip_prefix(char::is_alphabetic).unwrap(), "ey: välue");
+        let rest = text.strip_suffix(|c: char| !c.is_whitespace()).unwrap();
+        assert_eq!(rest, "key: välu");
+        assert_eq!(rest.as_ptr(), text.as_ptr());
+    }
+
+    #[test]
+    fn text_split_once() {
+        let none = Text::from("no delimiter");
+        assert!(none.split_once('=').is_none());
+        assert!(none.rsplit_once('=').is_none());
+        assert!(none.split_once(": ").is_none());
+        assert!(none.rsplit_once(": ").is_none());
+
+        let once = Text::from("clé=välue");
+        let (a, b) = once.split_once('=').unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("clé", "välue"));
+        assert_eq!(b.as_ptr(), once[5..].as_ptr());
+        let (a, b) = once.rsplit_once('=').unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("clé", "välue"));
+
+        let many = Text::from("a→b→c");
+        let (a, b) = many.split_once('→').unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("a", "b→c"));
+        let (a, b) = many.rsplit_once('→').unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("a→b", "c"));
+
+        let headers = Text::from("X: 1: 2");
+        let (a, b) = headers.split_once(": ").unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("X", "1: 2"));
+        let (a, b) = headers.rsplit_once(": ").unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("X: 1", "2"));
+
+        // delimiters at the ends give empty halves
+        let (a, b) = Text::from("=x=").split_once('=').unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("", "x="));
+        let (a, b) = Text::from("=x=").rsplit_once('=').unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("=x", ""));
+
+        // other patterns, like `str::split_once`
+        let line = Text::from("key =\tvalue");
+        let (a, b) = line.split_once(char::is_whitespace).unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("key", "=\tvalue"));
+        assert_eq!(b.as_ptr(), line[4..].as_ptr());
+        let (a, b) = line.rsplit_once(|c: char| c.is_whitespace()).unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("key =", "value"));
+        let (a, b) = line.split_once(['=', '\t']).unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("key ", "\tvalue"));
+        let (a, b) = line.split_once("").unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("", "key =\tvalue"));
+    }
+
+    #[test]
+    fn text_from_utf8_error_returns_bytes() {
+        let buf = Bytes::from_static(b"valid \xc3\x28 then not");
+        let err = Text::from_utf8(buf.clone()).unwrap_err();
+        assert_eq!(err.utf8_error().valid_up_to(), 6);
+        assert_eq!(err.as_bytes(), &buf[..]);
+        let back = err.into_bytes();
+        assert_eq!(back, buf);
+        assert_eq!(back.as_ptr(), buf.as_ptr());
+
+        let err = Text::try_from(Bytes::from_static(b"\xff")).unwrap_err();
+        assert_eq!(err.into_bytes(), Bytes::from_static(b"\xff"));
+    }
+
+    #[test]
+    fn text_from_cow() {
+        let s: &'static str = "borrowed";
+        let text = Text::from(Cow::Borrowed(s));
+        assert_eq!(text, "borrowed");
+        assert_eq!(text.as_ptr(), s.as_ptr());
+
+        let owned = String::from("owned");
+        let ptr = owned.as_ptr();
+        let text = Text::from(Cow::<'static, str>::Owned(owned));
+        assert_eq!(text, "owned");
+        assert_eq!(text.as_ptr(), ptr);
+
+        let cow: Cow<'_, str> = Cow::from(&text);
+        assert!(matches!(cow, Cow::Owned(_)));
+        assert_eq!(cow, "owned");
+    }
+
+    #[test]
+    fn text_repeat() {
+        let text = Text::from("é🦀");
+        assert_eq!(text.repeat(0), "");
+        let once = text.repeat(1);
+        assert_eq!(once, "é🦀");
+        assert_eq!(once.as_ptr(), text.as_ptr());
+        assert_eq!(text.repeat(3), "é🦀é🦀é🦀");
+        assert_eq!(Text::new().repeat(5), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn text_repeat_overflow() {
+        Text::from("ab").repeat(usize::MAX / 2 + 1);
+    }
+
+    #[test]
+    fn text_concat() {
+        let fragments = ["größe", " 🦀 ", "", "naïve", "日本"];
+        let parts: Vec<Text> = fragments.iter().map(|s| Text::from(*s)).collect();
+        let expected: String = fragments.concat();
+        assert_eq!(Text::concat(&parts), expected.as_str());
+        assert_eq!(Text::concat_iter(parts.clone()), expected.as_str());
+
+        let single = Text::from("only");
+        let concatenated = Text::concat(core::slice::from_ref(&single));
+        assert_eq!(concatenated.as_ptr(), single.as_ptr());
+        let concatenated = Text::concat_iter(Some(single.clone()));
+        assert_eq!(concatenated.as_ptr(), single.as_ptr());
+
+        assert_eq!(Text::concat(&[]), "");
+        assert_eq!(Text::concat_iter(Vec::new()), "");
+    }
+
+    #[test]
+    fn text_replace() {
+        let text = Text::from("naïve café");
+        let same = text.replace("xyz", "abc");
+        assert_eq!(same, "naïve café");
+        assert_eq!(same.as_ptr(), text.as_ptr());
+        assert_eq!(text.replacen("é", "e", 0).as_ptr(), text.as_ptr());
+
+        // matches are taken left to right without overlapping
+        let text = Text::from("aaaaa");
+        assert_eq!(
+            text.replace("aa", "b"),
+            str::replace(&text, "aa", "b").as_str()
+        );
+        assert_eq!(text.replace("aa", "b"), "bba");
+
+        let text = Text::from("ï-ï-ï");
+        assert_eq!(text.replace("ï", "🦀🦀"), "🦀🦀-🦀🦀-🦀🦀");
+        assert_eq!(text.replace("-ï", ""), "ï");
+        assert_eq!(text.replacen("ï", "i", 2), "i-i-ï");
+        assert_eq!(text.replacen("ï", "i", 10), "i-i-i");
+
+        let text = Text::from("ab");
+        assert_eq!(text.replace("", "-"), "-a-b-");
+
+        // chars, sets of chars and closures, like `str::replace`
+        let text = Text::from("a b\tc 🦀");
+        assert_eq!(text.replace(' ', ""), "ab\tc🦀");
+        assert_eq!(text.replace(char::is_whitespace, "_"), "a_b_c_🦀");
+        assert_eq!(text.replacen(char::is_whitespace, "", 1), "ab\tc 🦀");
+        assert_eq!(text.replace(['a', '🦀'], "é"), "é b\tc é");
+        assert_eq!(text.replace(|c: char| !c.is_ascii(), "crab"), "a b\tc crab");
+        let same = text.replace('x', "y");
+        assert_eq!(same.as_ptr(), text.as_ptr());
+    }
+
+    #[test]
+    fn text_try_from_byte_slices() {
+        let text = Text::try_from("grüß 🦀".as_bytes()).unwrap();
+        assert_eq!(text, "grüß 🦀");
+        let err = Text::try_from(&b"ok\xff"[..]).unwrap_err();
+        assert_eq!(err.valid_up_to(), 2);
+
+        let vec = "grüß 🦀".as_bytes().to_vec();
+        let ptr = vec.as_ptr();
+        let text = Text::try_from(vec).unwrap();
+        assert_eq!(text, "grüß 🦀");
+        assert_eq!(text.as_ptr(), ptr);
+
+        let err = Text::try_from(b"\xc3 bad".to_vec()).unwrap_err();
+        assert_eq!(err.utf8_error().valid_up_to(), 0);
+        assert_eq!(err.into_bytes(), b"\xc3 bad");
+    }
+
+    #[test]
+    fn text_from_box_and_arc_str() {
+        let boxed: Box<str> = "boxed 🦀".into();
+        let ptr = boxed.as_ptr();
+        let text = Text::from(boxed);
+        assert_eq!(text, "boxed 🦀");
+        assert_eq!(text.as_ptr(), ptr);
+
+        let shared: Arc<str> = "shared 🦀".into();
+        let text = Text::from(Arc::clone(&shared));
+        assert_eq!(text, "shared 🦀");
+        assert_eq!(text.as_ptr(), shared.as_ptr());
+        assert_eq!(Arc::strong_count(&shared), 2);
+        let slice = text.get(7..).unwrap();
+        drop(text);
+        assert_eq!(Arc::strong_count(&shared), 2);
+        assert_eq!(slice, "🦀");
+        drop(slice);
+        assert_eq!(Arc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn text_trim_matches() {
+        let text = Text::from("ééaéé");
+        let trimmed = text.trim_matches('é');
+        assert_eq!(trimmed, "a");
+        assert_eq!(trimmed.as_ptr(), text[4..].as_ptr());
+        assert_eq!(text.trim_start_matches('é'), "aéé");
+        assert_eq!(text.trim_end_matches('é'), "ééa");
+        assert_eq!(text.trim_matches('x'), "ééaéé");
+
+        // closures, fn items and sets of chars, like `str::trim_matches`
+        let trimmed = text.trim_matches(|c: char| !c.is_ascii());
+        assert_eq!(trimmed, "a");
+        assert_eq!(trimmed.as_ptr(), text[4..].as_ptr());
+        assert_eq!(text.trim_start_matches(char::is_alphabetic), "");
+        let set: &[char] = &['é', 'x'];
+        assert_eq!(text.trim_matches(set), "a");
+        assert_eq!(text.trim_start_matches(['a', 'é']), "");
+        let one = &['é'];
+        assert_eq!(text.trim_end_matches(one), "ééa");
+        let mut count = 0;
+        let trimmed = text.trim_end_matches(|_| {
+            count += 1;
+            count < 2
+        });
+        assert_eq!(trimmed, "ééaé");
+
+        let all = Text::from("🦀🦀🦀");
+        assert_eq!(all.trim_matches('🦀'), "");
+        assert_eq!(all.trim_start_matches('🦀'), "");
+        assert_eq!(all.trim_end_matches('🦀'), "");
+        assert_eq!(all.trim_start_matches("🦀🦀"), "🦀");
+        assert_eq!(all.trim_end_matches("🦀🦀"), "🦀");
+
+        let text = Text::from("abababcab");
+        assert_eq!(text.trim_start_matches("ab"), "cab");
+        assert_eq!(text.trim_end_matches("ab"), "abababc");
+        assert_eq!(text.trim_start_matches(""), "abababcab");
+    }
+
+    #[test]
+    fn text_subtext() {
+        let text = Text::from("añb🦀c");
+        for start in 0..=text.len() {
+            for end in 0..=text.len() + 1 {
+                let got = text.get(start..end);
+                assert_eq!(text.subtext(start, end), got);
+                if let Some(got) = got {
+                    let unchecked = unsafe { text.subtext_unchecked(start, end) };
+                    assert_eq!(unchecked, got);
+                    assert_eq!(unchecked.as_ptr(), got.as_ptr());
+                }
+            }
+        }
+        assert_eq!(text.subtext(3, 8).unwrap(), "b🦀");
+        assert!(text.subtext(4, 6).is_none());
+    }
+
+    #[test]
+    fn text_find_rfind() {
+        let text = Text::from("añb🦀añb");
+        let s: &str = &text;
+        assert_eq!(text.find('ñ'), s.find('ñ'));
+        assert_eq!(text.find('ñ'), Some(1));
+        assert_eq!(text.rfind('ñ'), Some(9));
+        assert_eq!(text.find("b🦀a"), Some(3));
+        assert_eq!(text.rfind("añ"), Some(8));
+        assert_eq!(text.find(&String::from("🦀")), Some(4));
+        assert_eq!(text.find(&['🦀', 'b'][..]), Some(3));
+        assert_eq!(text.rfind(['🦀', 'a']), Some(8));
+        let set = &['x', '🦀'];
+        assert_eq!(text.find(set), Some(4));
+        assert_eq!(text.find('x'), None);
+        assert_eq!(text.rfind("b🦀b"), None);
+        assert_eq!(text.find(""), Some(0));
+        assert_eq!(text.rfind(""), Some(text.len()));
+
+        // closures and fn items, like `str::find`
+        assert_eq!(text.find(|c: char| !c.is_ascii()), Some(1));
+        assert_eq!(text.rfind(|c: char| c == 'a'), Some(8));
+        assert_eq!(text.find(char::is_alphabetic), Some(0));
+        assert_eq!(text.rfind(char::is_whitespace), None);
+        let mut seen = 0;
+        assert_eq!(
+            text.find(|_| {
+                seen += 1;
+                seen == 3
+            }),
+            Some(3)
+        );
+
+        // `&&str`, as from iterating over a slice of needles
+        for needle in &["🦀", "añb"] {
+            assert_eq!(text.find(needle), str::find(&text, needle));
+            assert_eq!(text.rfind(needle), str::rfind(&text, needle));
+        }
+        let needle = String::from("ñb");
+        assert_eq!(text.rfind(&needle), Some(9));
+    }
+
+    #[test]
+    fn text_escape_json() {
+        let plain = Text::from("plain naïve text 🦀");
+        let escaped = plain.escape_json();
+        assert_eq!(escaped, "plain naïve text 🦀");
+        assert_eq!(escaped.as_ptr(), plain.as_ptr());
+
+        let text = Text::from("\"quoted\" back\\slash\nnew\ttab\r\u{8}\u{c}\u{1f} ünï");
+        let escaped = text.escape_json();
+        assert_eq!(
+            escaped,
+            r#"\"quoted\" back\\slash\nnew\ttab\r\b\f\u001f ünï"#
+        );
+        // the escaped text parses back to the original
+        let json = format!("\"{}\"", escaped);
+        let parsed: String = serde_json::from_str(&json).unwrap();
+        assert_eq!(text, parsed);
+    }
+
+    #[test]
+    fn text_escape_default() {
+        let plain = Text::from("plain ASCII (text)");
+        let escaped = plain.escape_default();
+        assert_eq!(escaped, "plain ASCII (text)");
+        assert_eq!(escaped.as_ptr(), plain.as_ptr());
+
+        let text = Text::from("\"q\" 'a'\\\n\t\r é🦀\u{7f}");
+        let expected = str::escape_default(&text).to_string();
+        assert_eq!(text.escape_default(), expected.as_str());
     }
 }
@@ -1,15 +1,17 @@
-use std::{
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, string::String, sync::Arc, vec::Vec};
+use core::{
     borrow::{Borrow, BorrowMut},
     convert::TryFrom,
-    fmt::{Debug, Display},
+    fmt::{Debug, Display, Write},
     hash::Hash,
-    ops::{Deref, DerefMut},
+    iter::FromIterator,
+    ops::{Deref, DerefMut, RangeBounds},
     str::Utf8Error,
 };
 
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 
-use crate::Text;
+use crate::{text::char_range, FromUtf8Error, Text};
 
 /// Mutable UTF-8 text buffer
 ///
@@ -37,11 +39,14 @@ use crate::Text;
 /// ```
 // example taken from `bytes`
 #[derive(Default)]
-pub struct TextMut(BytesMut);
+pub struct TextMut(BytesMut, LineEnding);
 
 impl TextMut {
     /// Creates a new, empty, text buffer.
     ///
+    /// Unlike [`Text::new`], this isn't a `const fn`, since `BytesMut::new`
+    /// isn't one either. Use [`Text::new`] for an empty constant instead.
+    ///
     /// # Example
     ///
     /// ```
@@ -51,7 +56,7 @@ impl TextMut {
     /// println!("{}", text);
     /// ```
     pub fn new() -> Self {
-        Self(BytesMut::new())
+        Self(BytesMut::new(), LineEnding::Lf)
     }
 
     /// Creates a new, empty, text buffer that can grow to at least `capacity`
@@ -66,7 +71,7 @@ impl TextMut {
     /// println!("{}", text);
     /// ```
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(BytesMut::with_capacity(capacity))
+        Self(BytesMut::with_capacity(capacity), LineEnding::Lf)
     }
 
     /// Copies the provided string into a new mutable buffer.
@@ -101,10 +106,39 @@ impl TextMut {
     /// text.push_str(" world!");
     /// assert_eq!(text, "Hello, world!");
     /// ```
-    pub fn from_utf8(b: BytesMut) -> Result<Self, Utf8Error> {
+    ///
+    /// If `b` isn't valid UTF-8, the returned error holds on to it so it can be
+    /// recovered with [`FromUtf8Error::into_bytes`].
+    pub fn from_utf8(b: BytesMut) -> Result<Self, FromUtf8Error<BytesMut>> {
         // run utf-8 validation
-        let _ = std::str::from_utf8(b.as_ref())?;
-        Ok(Self(b))
+        match core::str::from_utf8(b.as_ref()) {
+            Ok(_) => Ok(Self(b, LineEnding::Lf)),
+            Err(e) => Err(FromUtf8Error::new(b, e)),
+        }
+    }
+
+    /// Converts `BytesMut` to `TextMut`, replacing any invalid UTF-8 sequences
+    /// with `U+FFFD REPLACEMENT CHARACTER`
+    ///
+    /// If `b` is already valid UTF-8, it is wrapped without copying.
+    /// Otherwise, a new buffer is allocated to hold the replaced text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// # use bytes::{BytesMut, BufMut};
+    /// let mut buf = BytesMut::new();
+    /// buf.put(&b"Hello \xF0\x90\x80World"[..]);
+    ///
+    /// let text = TextMut::from_utf8_lossy(buf);
+    /// assert_eq!(text, "Hello �World");
+    /// ```
+    pub fn from_utf8_lossy(b: BytesMut) -> Self {
+        match String::from_utf8_lossy(b.as_ref()) {
+            Cow::Borrowed(_) => Self(b, LineEnding::Lf),
+            Cow::Owned(s) => Self::copy_from(s),
+        }
     }
 
     /// Converts `Bytes` to `Text` without verifying that it's valid UTF-8
@@ -128,7 +162,7 @@ impl TextMut {
     /// ```
     #[inline]
     pub const unsafe fn from_utf8_unchecked(b: BytesMut) -> Self {
-        Self(b)
+        Self(b, LineEnding::Lf)
     }
 
     /// The number of bytes in this text
@@ -214,6 +248,164 @@ impl TextMut {
         self.0.reserve(additional)
     }
 
+    /// Reserves space for at least `additional` more bytes, growing the
+    /// capacity geometrically if it has to grow at all
+    ///
+    /// If there isn't already room for `additional` more bytes, the capacity
+    /// grows to at least double its current size. Compared to
+    /// [`TextMut::reserve`], this can over-allocate, but many small appends in
+    /// a row only cause O(log n) reallocations instead of one each.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::with_capacity(64);
+    /// text.push_str(&"a".repeat(64));
+    /// text.reserve_amortized(1);
+    /// assert!(text.capacity() >= 128);
+    /// ```
+    pub fn reserve_amortized(&mut self, additional: usize) {
+        let len = self.len();
+        let capacity = self.capacity();
+        if capacity - len >= additional {
+            return;
+        }
+        let target = len
+            .saturating_add(additional)
+            .max(capacity.saturating_mul(2));
+        self.0.reserve(target - len)
+    }
+
+    /// Reserves space for exactly `additional` more bytes, without any room
+    /// to spare
+    ///
+    /// Does nothing if there's already room. Otherwise, since `BytesMut`
+    /// can grow by more than it's asked to, this allocates a new buffer of
+    /// exactly the right size and copies the text into it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity would overflow `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("header");
+    /// text.reserve_exact(10);
+    /// assert_eq!(text.capacity(), 16);
+    /// ```
+    pub fn reserve_exact(&mut self, additional: usize) {
+        if self.capacity() - self.len() >= additional {
+            return;
+        }
+        let capacity = self
+            .len()
+            .checked_add(additional)
+            .expect("capacity overflow");
+        let mut buf = BytesMut::with_capacity(capacity);
+        buf.extend_from_slice(&self.0);
+        self.0 = buf;
+    }
+
+    /// Shrinks the capacity of this buffer to match its length
+    ///
+    /// `BytesMut` can't give memory back on its own, so if there's any spare
+    /// capacity, this allocates a new buffer of exactly the right size and
+    /// copies the text into it, taking O(n) time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::with_capacity(1024);
+    /// text.push_str("small");
+    /// text.shrink_to_fit();
+    /// assert_eq!(text.capacity(), 5);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        if self.capacity() > self.len() {
+            self.0 = BytesMut::from(&self.0[..]);
+        }
+    }
+
+    /// Lets `f` write up to `additional` bytes directly onto the end of this
+    /// buffer
+    ///
+    /// `f` is given a slice of `additional` bytes past the end of the text,
+    /// and returns how many bytes it wrote to the start of it. Only those
+    /// newly written bytes are checked to be valid UTF-8 before they're added
+    /// to the text, so a char cut off at the end of them is an error. The
+    /// slice is zeroed before `f` gets it, since handing out uninitialized
+    /// memory as `&mut [u8]` isn't sound, but that's still cheaper than
+    /// filling a separate buffer and copying it in.
+    ///
+    /// If the written bytes aren't valid UTF-8 (or `f` panics), the text is
+    /// left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` returns more than `additional`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("read: ");
+    /// text.reserve_and_fill(16, |buf| {
+    ///     buf[..5].copy_from_slice(b"hello");
+    ///     5
+    /// })
+    /// .unwrap();
+    /// assert_eq!(text, "read: hello");
+    ///
+    /// assert!(text.reserve_and_fill(16, |buf| {
+    ///     buf[0] = 0xff;
+    ///     1
+    /// })
+    /// .is_err());
+    /// assert_eq!(text, "read: hello");
+    /// ```
+    pub fn reserve_and_fill<F: FnOnce(&mut [u8]) -> usize>(
+        &mut self,
+        additional: usize,
+        f: F,
+    ) -> Result<(), Utf8Error> {
+        /// Truncates the buffer back to `len` when dropped, so that it's
+        /// valid UTF-8 even if `f` panics
+        struct Guard<'a> {
+            buf: &'a mut BytesMut,
+            len: usize,
+        }
+
+        impl Drop for Guard<'_> {
+            fn drop(&mut self) {
+                self.buf.truncate(self.len);
+            }
+        }
+
+        let len = self.len();
+        let guard = Guard {
+            buf: &mut self.0,
+            len,
+        };
+        guard.buf.resize(len + additional, 0);
+        let written = f(&mut guard.buf[len..]);
+        assert!(
+            written <= additional,
+            "wrote {} bytes, but only {} were reserved",
+            written,
+            additional
+        );
+        let mut guard = guard;
+        let result = core::str::from_utf8(&guard.buf[len..len + written]);
+        if result.is_ok() {
+            guard.len = len + written;
+        }
+        result.map(|_| ())
+    }
+
     /// Clears the buffer of its contents
     ///
     /// # Example
@@ -291,6 +483,29 @@ impl TextMut {
         self.0
     }
 
+    /// Converts into a `String`
+    ///
+    /// If this is the only handle to the underlying buffer, the buffer is
+    /// reused without reallocating, though the text is moved to the start of
+    /// it if something was split off the front. Otherwise (e.g. if the other
+    /// half of a split is still around), the contents are copied into a new
+    /// `String`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("Hello");
+    /// text.push_str(", world!");
+    /// let string: String = text.into_string();
+    /// assert_eq!(string, "Hello, world!");
+    /// ```
+    pub fn into_string(self) -> String {
+        let vec = Vec::from(self.0);
+        // Safety: the bytes came from `self`, which is valid UTF-8
+        unsafe { String::from_utf8_unchecked(vec) }
+    }
+
     /// Splits the text into two halves
     ///
     /// Returns `Err(self)` if the index is not a valid char boundary
@@ -307,7 +522,7 @@ impl TextMut {
     pub fn split_at(mut self, index: usize) -> Result<(Self, Self), Self> {
         soft_assert::soft_assert!(self.is_char_boundary(index), Err(self));
         let right = self.0.split_off(index);
-        Ok((Self(self.0), Self(right)))
+        Ok((Self(self.0, self.1), Self(right, self.1)))
     }
 
     /// Splits the text into two halves, `self` being the start half and
@@ -328,7 +543,7 @@ impl TextMut {
     pub fn split_off(&mut self, index: usize) -> Option<Self> {
         soft_assert::soft_assert!(self.is_char_boundary(index));
         let right = self.0.split_off(index);
-        Some(Self(right))
+        Some(Self(right, self.1))
     }
 
     /// Splits the text into two halves, `self` being the end half and
@@ -349,12 +564,175 @@ impl TextMut {
     pub fn split_to(&mut self, index: usize) -> Option<Self> {
         soft_assert::soft_assert!(self.is_char_boundary(index));
         let right = self.0.split_to(index);
-        Some(Self(right))
+        Some(Self(right, self.1))
+    }
+
+    /// Shortens the buffer to `new_len` bytes
+    ///
+    /// Does nothing if the buffer is already `new_len` bytes or shorter. Like
+    /// [`TextMut::clear`], the capacity is conserved.
+    ///
+    /// Returns `false` and leaves the buffer unchanged if `new_len` is not a
+    /// valid char boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("née 🦀");
+    /// assert!(text.truncate(4));
+    /// assert_eq!(text, "née");
+    /// assert!(!text.truncate(2));
+    /// ```
+    pub fn truncate(&mut self, new_len: usize) -> bool {
+        if new_len >= self.len() {
+            return true;
+        }
+        soft_assert::soft_assert!(self.is_char_boundary(new_len), false);
+        self.0.truncate(new_len);
+        true
+    }
+
+    /// Shortens the buffer, keeping only the first `char_count` chars
+    ///
+    /// Does nothing if the buffer has `char_count` chars or fewer. Like
+    /// [`TextMut::clear`], the capacity is conserved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("née 🦀 rust");
+    /// text.truncate_chars(5);
+    /// assert_eq!(text, "née 🦀");
+    /// ```
+    pub fn truncate_chars(&mut self, char_count: usize) {
+        if let Some((index, _)) = self.char_indices().nth(char_count) {
+            self.0.truncate(index);
+        }
+    }
+
+    /// Splits off the start of the text up to `index` and freezes it,
+    /// leaving the rest in `self`
+    ///
+    /// This is handy for draining complete frames from the front of a buffer
+    /// that is still being written to.
+    ///
+    /// Returns `None` if the index is not a valid char boundary. If this
+    /// returns `None`, `self` remains unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut buf = TextMut::copy_from("PING\nPO");
+    /// let frame = buf.take_frozen_to(5).unwrap();
+    /// assert_eq!(frame, "PING\n");
+    /// buf.push_str("NG\n");
+    /// assert_eq!(buf, "PONG\n");
+    /// ```
+    pub fn take_frozen_to(&mut self, index: usize) -> Option<Text> {
+        self.split_to(index).map(TextMut::freeze)
+    }
+
+    /// Removes the given byte range from this buffer, and returns it in a new
+    /// buffer
+    ///
+    /// The text after the range is shifted over to close the gap, so this
+    /// takes O(n) time in the length of that text. The removed text is
+    /// copied into its own buffer, which uses the same [`LineEnding`] as this
+    /// one.
+    ///
+    /// Returns `None` and leaves the buffer unchanged if either end of the
+    /// range isn't a char boundary, or the range is out of order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("one two three");
+    /// let mut removed = text.drain(4..8).unwrap();
+    /// removed.push_str("and ");
+    /// assert_eq!(removed, "two and ");
+    /// assert_eq!(text, "one three");
+    /// ```
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Option<TextMut> {
+        let (start, end) = char_range(self, range)?;
+        let mut removed = TextMut::copy_from(&self[start..end]);
+        removed.1 = self.1;
+        self.0.copy_within(end.., start);
+        self.0.truncate(self.len() - (end - start));
+        Some(removed)
+    }
+
+    /// Removes the given byte range from this buffer, and returns it as a
+    /// frozen text
+    ///
+    /// This is the same as [`TextMut::drain`], but freezes the removed text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("Hello, cruel world!");
+    /// let removed = text.drain_text(7..13).unwrap();
+    /// assert_eq!(removed, "cruel ");
+    /// assert_eq!(text, "Hello, world!");
+    /// ```
+    pub fn drain_text<R: RangeBounds<usize>>(&mut self, range: R) -> Option<Text> {
+        self.drain(range).map(TextMut::freeze)
+    }
+
+    /// Gets a mutable view of part of this buffer
+    ///
+    /// Returns `None` if either end of the range isn't a char boundary, or
+    /// the range is out of order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("id=abc; name=ünï");
+    /// text.get_mut(3..6).unwrap().make_ascii_uppercase();
+    /// assert_eq!(text, "id=ABC; name=ünï");
+    /// assert!(text.get_mut(14..).is_none());
+    /// ```
+    pub fn get_mut<R: RangeBounds<usize>>(&mut self, range: R) -> Option<&mut str> {
+        let (start, end) = char_range(self, range)?;
+        Some(&mut self.as_str_mut()[start..end])
+    }
+
+    /// Splits the text into two halves at the boundary before the
+    /// `char_index`th char
+    ///
+    /// Returns `Err(self)` if `char_index` is greater than the number of chars
+    /// in the text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let text = TextMut::copy_from("día a día");
+    /// let (a, b) = text.split_at_char(3).unwrap();
+    /// assert_eq!(a, "día");
+    /// assert_eq!(b, " a día");
+    /// ```
+    pub fn split_at_char(self, char_index: usize) -> Result<(Self, Self), Self> {
+        let index = self
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(core::iter::once(self.len()))
+            .nth(char_index);
+        match index {
+            Some(index) => self.split_at(index),
+            None => Err(self),
+        }
     }
 
     /// Copies the string reference into this buffer
     ///
-    /// If you're pushing another `TextMut`, it's better to use [`TextMut::join`](TextMut::join)
+    /// If you're pushing another `TextMut`, it's better to use
+    /// [`TextMut::push_text`], which can avoid the copy
     ///
     /// # Example
     ///
@@ -374,6 +752,32 @@ impl TextMut {
         self.0.extend_from_slice(s.as_ref().as_bytes())
     }
 
+    /// Appends another `TextMut` to the end of this buffer
+    ///
+    /// If `other` was split off from right after this buffer, they're put
+    /// back together in O(1) time, like [`TextMut::join`]. Otherwise, `other`
+    /// is copied in like [`TextMut::push_str`]. Unlike `join`, this only
+    /// borrows `self`, so it's easy to use in a loop.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("Hello, world!");
+    /// let rest = text.split_off(5).unwrap();
+    ///
+    /// // no copy, since `rest` came from right after `text`
+    /// text.push_text(rest);
+    /// // copied, since this came from somewhere else
+    /// text.push_text(TextMut::copy_from(" Bye!"));
+    /// assert_eq!(text, "Hello, world! Bye!");
+    /// ```
+    pub fn push_text(&mut self, other: TextMut) {
+        if let Err(other) = self.try_join(other) {
+            self.push_str(other);
+        }
+    }
+
     /// Adds a character to the end of this buffer
     ///
     /// # Example
@@ -394,6 +798,350 @@ impl TextMut {
         self.push_str(s);
     }
 
+    /// Formats `value` directly onto the end of this buffer
+    ///
+    /// This avoids the intermediate `String` that
+    /// `text.push_str(value.to_string())` would allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("answer: ");
+    /// text.push_display(&42);
+    /// assert_eq!(text, "answer: 42");
+    /// ```
+    pub fn push_display<T: Display + ?Sized>(&mut self, value: &T) {
+        // writing to a `TextMut` can't fail
+        let _ = write!(self, "{}", value);
+    }
+
+    /// Copies the string reference into this buffer, followed by the line
+    /// ending set with [`TextMut::set_line_ending`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::{LineEnding, TextMut};
+    /// let mut text = TextMut::new();
+    /// text.set_line_ending(LineEnding::CrLf);
+    /// text.writeln_str("HTTP/1.1 200 OK");
+    /// text.writeln_str("");
+    /// assert_eq!(text, "HTTP/1.1 200 OK\r\n\r\n");
+    /// ```
+    pub fn writeln_str(&mut self, s: &str) {
+        self.push_str(s);
+        self.push_str(self.1.as_str());
+    }
+
+    /// Sets the line ending that [`TextMut::writeln_str`] uses
+    ///
+    /// This is [`LineEnding::Lf`] by default. Texts split off from this
+    /// buffer keep its line ending.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::{LineEnding, TextMut};
+    /// let mut text = TextMut::new();
+    /// text.writeln_str("unix");
+    /// text.set_line_ending(LineEnding::CrLf);
+    /// text.writeln_str("windows");
+    /// assert_eq!(text, "unix\nwindows\r\n");
+    /// ```
+    pub fn set_line_ending(&mut self, ending: LineEnding) {
+        self.1 = ending;
+    }
+
+    /// The line ending that [`TextMut::writeln_str`] uses
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::{LineEnding, TextMut};
+    /// let mut text = TextMut::new();
+    /// assert_eq!(text.line_ending(), LineEnding::Lf);
+    /// text.set_line_ending(LineEnding::CrLf);
+    /// assert_eq!(text.line_ending(), LineEnding::CrLf);
+    /// ```
+    pub fn line_ending(&self) -> LineEnding {
+        self.1
+    }
+
+    /// Copies the string reference to the start of this buffer
+    ///
+    /// Unlike [`TextMut::push_str`], this has to shift the existing contents
+    /// over to make room, so it takes O(n) time in the length of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("world!");
+    /// text.prepend_str("Hello, ");
+    /// assert_eq!(text, "Hello, world!");
+    /// ```
+    pub fn prepend_str(&mut self, s: impl AsRef<str>) {
+        let s = s.as_ref();
+        self.0.extend_from_slice(s.as_bytes());
+        // moves `s` from the end to the start, shifting everything else over
+        self.0.rotate_right(s.len());
+    }
+
+    /// Adds a character to the start of this buffer
+    ///
+    /// Unlike [`TextMut::push`], this has to shift the existing contents over
+    /// to make room, so it takes O(n) time in the length of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("ello");
+    /// text.prepend('H');
+    /// assert_eq!(text, "Hello");
+    /// ```
+    pub fn prepend(&mut self, c: char) {
+        let mut buf = [0; 4];
+        let s = c.encode_utf8(&mut buf);
+        self.prepend_str(s);
+    }
+
+    /// Copies the string reference into this buffer at the byte index `idx`
+    ///
+    /// Like [`TextMut::prepend_str`], this has to shift the contents after
+    /// `idx` over to make room, so it takes O(n) time.
+    ///
+    /// Returns `false` and leaves the buffer unchanged if `idx` is not a
+    /// valid char boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("Hello!");
+    /// assert!(text.insert_str(5, ", world"));
+    /// assert_eq!(text, "Hello, world!");
+    /// ```
+    pub fn insert_str(&mut self, idx: usize, s: impl AsRef<str>) -> bool {
+        soft_assert::soft_assert!(self.is_char_boundary(idx), false);
+        let s = s.as_ref();
+        self.0.extend_from_slice(s.as_bytes());
+        // moves `s` from the end to `idx`, shifting everything after it over
+        self.0[idx..].rotate_right(s.len());
+        true
+    }
+
+    /// Adds a character to this buffer at the byte index `idx`
+    ///
+    /// Like [`TextMut::prepend`], this has to shift the contents after `idx`
+    /// over to make room, so it takes O(n) time.
+    ///
+    /// Returns `false` and leaves the buffer unchanged if `idx` is not a
+    /// valid char boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("Hllo");
+    /// assert!(text.insert(1, 'e'));
+    /// assert_eq!(text, "Hello");
+    /// ```
+    pub fn insert(&mut self, idx: usize, c: char) -> bool {
+        let mut buf = [0; 4];
+        let s = c.encode_utf8(&mut buf);
+        self.insert_str(idx, s)
+    }
+
+    /// Removes the char starting at the byte index `idx` and returns it
+    ///
+    /// Like [`TextMut::insert`], this has to shift the contents after the
+    /// char back to fill the gap, so it takes O(n) time.
+    ///
+    /// Returns `None` and leaves the buffer unchanged if `idx` is not a valid
+    /// char boundary, or is the end of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("crab🦀s");
+    /// assert_eq!(text.remove(4), Some('🦀'));
+    /// assert_eq!(text, "crabs");
+    /// assert_eq!(text.remove(5), None);
+    /// ```
+    pub fn remove(&mut self, idx: usize) -> Option<char> {
+        soft_assert::soft_assert!(self.is_char_boundary(idx));
+        let c = self[idx..].chars().next()?;
+        let end = idx + c.len_utf8();
+        self.0.copy_within(end.., idx);
+        self.0.truncate(self.len() - c.len_utf8());
+        Some(c)
+    }
+
+    /// Keeps only the chars for which `f` returns `true`
+    ///
+    /// The kept chars are moved down in place, so this never reallocates.
+    /// If `f` panics, the buffer is left holding only the chars kept so far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("bell\u{7}, tab\t, ok");
+    /// text.retain(|c| !c.is_control());
+    /// assert_eq!(text, "bell, tab, ok");
+    /// ```
+    pub fn retain<F: FnMut(char) -> bool>(&mut self, mut f: F) {
+        /// Truncates the buffer to the kept chars when dropped, so that it's
+        /// valid UTF-8 even if `f` panics
+        struct Guard<'a> {
+            buf: &'a mut BytesMut,
+            len: usize,
+        }
+
+        impl Drop for Guard<'_> {
+            fn drop(&mut self) {
+                self.buf.truncate(self.len);
+            }
+        }
+
+        let mut guard = Guard {
+            buf: &mut self.0,
+            len: 0,
+        };
+        let mut read = 0;
+        while read < guard.buf.len() {
+            // `read` is always on a char boundary, and nothing from `read`
+            // onwards has been written to yet
+            let rest = unsafe { core::str::from_utf8_unchecked(&guard.buf[read..]) };
+            let c = match rest.chars().next() {
+                Some(c) => c,
+                None => break,
+            };
+            let char_len = c.len_utf8();
+            if f(c) {
+                guard.buf.copy_within(read..read + char_len, guard.len);
+                guard.len += char_len;
+            }
+            read += char_len;
+        }
+    }
+
+    /// Inserts a string at the boundary before the `grapheme_index`th
+    /// grapheme cluster
+    ///
+    /// Unlike inserting at a char index, this never lands inside a
+    /// user-perceived character (such as an emoji with modifiers, or a letter
+    /// with combining marks). Like [`TextMut::prepend_str`], this has to shift
+    /// the contents after the insertion point over, so it takes O(n) time.
+    ///
+    /// Returns `false` and leaves the buffer unchanged if `grapheme_index` is
+    /// greater than the number of grapheme clusters in the text.
+    ///
+    /// Requires the `unicode-segmentation` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("ne\u{301}e");
+    /// assert!(text.insert_at_grapheme(2, "-"));
+    /// assert_eq!(text, "ne\u{301}-e");
+    /// assert!(!text.insert_at_grapheme(5, "-"));
+    /// ```
+    #[cfg(feature = "unicode-segmentation")]
+    pub fn insert_at_grapheme(&mut self, grapheme_index: usize, s: &str) -> bool {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let index = self
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .chain(core::iter::once(self.len()))
+            .nth(grapheme_index);
+        match index {
+            Some(index) => self.insert_str(index, s),
+            None => false,
+        }
+    }
+
+    /// Converts all ASCII letters to lowercase, in place
+    ///
+    /// Non-ASCII chars are left as-is, so the length never changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("Content-TYPE: Ünïcode");
+    /// text.make_ascii_lowercase();
+    /// assert_eq!(text, "content-type: Ünïcode");
+    /// ```
+    pub fn make_ascii_lowercase(&mut self) {
+        self.0.make_ascii_lowercase();
+    }
+
+    /// Converts all ASCII letters to uppercase, in place
+    ///
+    /// Non-ASCII chars are left as-is, so the length never changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("Content-type: ünïcode");
+    /// text.make_ascii_uppercase();
+    /// assert_eq!(text, "CONTENT-TYPE: üNïCODE");
+    /// ```
+    pub fn make_ascii_uppercase(&mut self) {
+        self.0.make_ascii_uppercase();
+    }
+
+    /// Checks that this text and `other` are equal, ignoring ASCII case
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let text = TextMut::copy_from("Content-Type");
+    /// assert!(text.eq_ignore_ascii_case("CONTENT-type"));
+    /// assert!(!text.eq_ignore_ascii_case("Content-Length"));
+    /// ```
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.0.eq_ignore_ascii_case(other.as_bytes())
+    }
+
+    /// Converts each whitespace-separated word to ASCII title case, in place
+    ///
+    /// The first ASCII letter of each word is uppercased, and the rest of its
+    /// ASCII letters are lowercased. Anything before the first letter (like
+    /// punctuation) and all non-ASCII chars are left as-is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("the QUICK (brown) fox");
+    /// text.make_ascii_titlecase();
+    /// assert_eq!(text, "The Quick (Brown) Fox");
+    /// ```
+    pub fn make_ascii_titlecase(&mut self) {
+        let mut at_word_start = true;
+        for b in self.0.iter_mut() {
+            if b.is_ascii_whitespace() {
+                at_word_start = true;
+            } else if b.is_ascii_alphabetic() {
+                if at_word_start {
+                    b.make_ascii_uppercase();
+                    at_word_start = false;
+                } else {
+                    b.make_ascii_lowercase();
+                }
+            }
+        }
+    }
+
     /// Joins two `TextMut`s together
     ///
     /// If they were once contiguous (i.e. from one of the `split` methods) then
@@ -428,14 +1176,85 @@ impl TextMut {
         self
     }
 
+    /// Appends `other` to this buffer without copying, if `other` starts
+    /// right where this buffer's memory ends
+    ///
+    /// This is the case for the two halves of one of the `split` methods, so
+    /// they can be put back together in O(1) time. If `other` is anywhere
+    /// else, it's handed back as `Err(other)` and this buffer is left
+    /// unchanged; use [`TextMut::push_str`] or [`TextMut::join`] to copy it
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::TextMut;
+    /// let mut text = TextMut::copy_from("woohoo");
+    /// let tail = text.split_off(3).unwrap();
+    /// assert!(text.try_join(tail).is_ok());
+    /// assert_eq!(text, "woohoo");
+    ///
+    /// let elsewhere = TextMut::copy_from("!");
+    /// let elsewhere = text.try_join(elsewhere).unwrap_err();
+    /// assert_eq!(elsewhere, "!");
+    /// ```
+    pub fn try_join(&mut self, other: TextMut) -> Result<(), TextMut> {
+        let end = self.0.as_ptr() as usize + self.0.capacity();
+        if other.is_empty() || (self.len() == self.capacity() && end == other.0.as_ptr() as usize) {
+            self.0.unsplit(other.0);
+            Ok(())
+        } else {
+            Err(other)
+        }
+    }
+
+    /// Takes over the buffer of `s` without copying it, if possible
+    pub(crate) fn from_string(s: String) -> Self {
+        match Bytes::from(s.into_bytes()).try_into_mut() {
+            Ok(b) => Self(b, LineEnding::Lf),
+            // empty strings become static `Bytes`, which have to be copied
+            Err(b) => Self(BytesMut::from(&b[..]), LineEnding::Lf),
+        }
+    }
+
     fn as_str(&self) -> &str {
         // Safety:
         // `self` will always contain valid UTF-8
-        unsafe { std::str::from_utf8_unchecked(self.0.as_ref()) }
+        unsafe { core::str::from_utf8_unchecked(self.0.as_ref()) }
     }
 
     fn as_str_mut(&mut self) -> &mut str {
-        unsafe { std::str::from_utf8_unchecked_mut(self.0.as_mut()) }
+        unsafe { core::str::from_utf8_unchecked_mut(self.0.as_mut()) }
+    }
+}
+
+/// The line ending that [`TextMut::writeln_str`] adds after each line
+///
+/// Set with [`TextMut::set_line_ending`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum LineEnding {
+    /// `"\n"`, used on most platforms
+    #[default]
+    Lf,
+    /// `"\r\n"`, used on Windows and by many network protocols
+    CrLf,
+}
+
+impl LineEnding {
+    /// The line ending as a string
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bytes_text::LineEnding;
+    /// assert_eq!(LineEnding::Lf.as_str(), "\n");
+    /// assert_eq!(LineEnding::CrLf.as_str(), "\r\n");
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
     }
 }
 
@@ -447,6 +1266,12 @@ impl AsRef<str> for TextMut {
     }
 }
 
+impl AsRef<[u8]> for TextMut {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 impl AsMut<str> for TextMut {
     fn as_mut(&mut self) -> &mut str {
         self.as_str_mut()
@@ -479,8 +1304,16 @@ impl DerefMut for TextMut {
     }
 }
 
+impl From<Box<str>> for TextMut {
+    /// Copies the string into a new buffer, since `BytesMut` can't take over
+    /// an existing allocation
+    fn from(s: Box<str>) -> Self {
+        Self::copy_from(s)
+    }
+}
+
 impl TryFrom<BytesMut> for TextMut {
-    type Error = Utf8Error;
+    type Error = FromUtf8Error<BytesMut>;
 
     fn try_from(b: BytesMut) -> Result<Self, Self::Error> {
         Self::from_utf8(b)
@@ -502,24 +1335,36 @@ impl From<TextMut> for Text {
 // ## Formatting
 
 impl Display for TextMut {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         Display::fmt(self.as_str(), f)
     }
 }
 
 impl Debug for TextMut {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         Debug::fmt(self.as_str(), f)
     }
 }
 
+impl Write for TextMut {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
+
 // ## Comparisons
 
 // ### Self comparisons
 
 impl PartialEq for TextMut {
     fn eq(&self, other: &Self) -> bool {
-        (&**self).eq(&**other)
+        (**self).eq(&**other)
     }
 }
 
@@ -527,18 +1372,18 @@ impl Eq for TextMut {}
 
 impl PartialEq<&TextMut> for TextMut {
     fn eq(&self, other: &&TextMut) -> bool {
-        (&**self).eq(&***other)
+        (**self).eq(&***other)
     }
 }
 
 impl PartialEq<&mut TextMut> for TextMut {
     fn eq(&self, other: &&mut TextMut) -> bool {
-        (&**self).eq(&***other)
+        (**self).eq(&***other)
     }
 }
 
 impl PartialOrd for TextMut {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -547,37 +1392,37 @@ impl PartialOrd for TextMut {
 
 impl PartialEq<str> for TextMut {
     fn eq(&self, other: &str) -> bool {
-        (&**self).eq(other)
+        (**self).eq(other)
     }
 }
 
 impl PartialEq<&str> for TextMut {
     fn eq(&self, other: &&str) -> bool {
-        (&**self).eq(*other)
+        (**self).eq(*other)
     }
 }
 
 impl PartialEq<&mut str> for TextMut {
     fn eq(&self, other: &&mut str) -> bool {
-        (&**self).eq(*other)
+        (**self).eq(*other)
     }
 }
 
 impl PartialOrd<str> for TextMut {
-    fn partial_cmp(&self, other: &str) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(other)
+    fn partial_cmp(&self, other: &str) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(other)
     }
 }
 
 impl PartialOrd<&str> for TextMut {
-    fn partial_cmp(&self, other: &&str) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(*other)
+    fn partial_cmp(&self, other: &&str) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(*other)
     }
 }
 
 impl PartialOrd<&mut str> for TextMut {
-    fn partial_cmp(&self, other: &&mut str) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(*other)
+    fn partial_cmp(&self, other: &&mut str) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(*other)
     }
 }
 
@@ -585,37 +1430,37 @@ impl PartialOrd<&mut str> for TextMut {
 
 impl PartialEq<String> for TextMut {
     fn eq(&self, other: &String) -> bool {
-        (&**self).eq(other)
+        (**self).eq(other)
     }
 }
 
 impl PartialEq<&String> for TextMut {
     fn eq(&self, other: &&String) -> bool {
-        (&**self).eq(*other)
+        (**self).eq(*other)
     }
 }
 
 impl PartialEq<&mut String> for TextMut {
     fn eq(&self, other: &&mut String) -> bool {
-        (&**self).eq(*other)
+        (**self).eq(*other)
     }
 }
 
 impl PartialOrd<String> for TextMut {
-    fn partial_cmp(&self, other: &String) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&**other)
+    fn partial_cmp(&self, other: &String) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
     }
 }
 
 impl PartialOrd<&String> for TextMut {
-    fn partial_cmp(&self, other: &&String) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&***other)
+    fn partial_cmp(&self, other: &&String) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&***other)
     }
 }
 
 impl PartialOrd<&mut String> for TextMut {
-    fn partial_cmp(&self, other: &&mut String) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&***other)
+    fn partial_cmp(&self, other: &&mut String) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&***other)
     }
 }
 
@@ -623,49 +1468,87 @@ impl PartialOrd<&mut String> for TextMut {
 
 impl PartialEq<Text> for TextMut {
     fn eq(&self, other: &Text) -> bool {
-        (&**self).eq(&**other)
+        (**self).eq(&**other)
     }
 }
 
 impl PartialEq<&Text> for TextMut {
     fn eq(&self, other: &&Text) -> bool {
-        (&**self).eq(&***other)
+        (**self).eq(&***other)
     }
 }
 
 impl PartialEq<&mut Text> for TextMut {
     fn eq(&self, other: &&mut Text) -> bool {
-        (&**self).eq(&***other)
+        (**self).eq(&***other)
     }
 }
 
 impl PartialOrd<Text> for TextMut {
-    fn partial_cmp(&self, other: &Text) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&**other)
+    fn partial_cmp(&self, other: &Text) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
     }
 }
 
 impl PartialOrd<&Text> for TextMut {
-    fn partial_cmp(&self, other: &&Text) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&***other)
+    fn partial_cmp(&self, other: &&Text) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&***other)
     }
 }
 
 impl PartialOrd<&mut Text> for TextMut {
-    fn partial_cmp(&self, other: &&mut Text) -> Option<std::cmp::Ordering> {
-        (&**self).partial_cmp(&***other)
+    fn partial_cmp(&self, other: &&mut Text) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&***other)
+    }
+}
+
+// ### Box<str>, Rc<str>, and Arc<str> comparisons
+
+impl PartialEq<Box<str>> for TextMut {
+    fn eq(&self, other: &Box<str>) -> bool {
+        (**self).eq(&**other)
     }
 }
 
-/// ## Hash
+impl PartialEq<Rc<str>> for TextMut {
+    fn eq(&self, other: &Rc<str>) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl PartialEq<Arc<str>> for TextMut {
+    fn eq(&self, other: &Arc<str>) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl PartialOrd<Box<str>> for TextMut {
+    fn partial_cmp(&self, other: &Box<str>) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl PartialOrd<Rc<str>> for TextMut {
+    fn partial_cmp(&self, other: &Rc<str>) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl PartialOrd<Arc<str>> for TextMut {
+    fn partial_cmp(&self, other: &Arc<str>) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+// ## Hash
 
 impl Hash for TextMut {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        (&**self).hash(state);
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
     }
 }
 
-/// ## Extend
+// ## Extend
 
 impl Extend<char> for TextMut {
     fn extend<T>(&mut self, iter: T)
@@ -715,6 +1598,17 @@ impl Extend<Text> for TextMut {
     }
 }
 
+impl Extend<TextMut> for TextMut {
+    /// Buffers that were split off from right after this one are put back
+    /// without copying, like [`TextMut::push_text`]
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = TextMut>,
+    {
+        iter.into_iter().for_each(move |s| self.push_text(s));
+    }
+}
+
 impl<'a> Extend<&'a Text> for TextMut {
     fn extend<T>(&mut self, iter: T)
     where
@@ -724,6 +1618,62 @@ impl<'a> Extend<&'a Text> for TextMut {
     }
 }
 
+// ## FromIterator
+
+impl FromIterator<char> for TextMut {
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        let mut text = TextMut::new();
+        text.extend(iter);
+        text
+    }
+}
+
+impl<'a> FromIterator<&'a str> for TextMut {
+    fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
+        let mut text = TextMut::new();
+        text.extend(iter);
+        text
+    }
+}
+
+impl FromIterator<String> for TextMut {
+    fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+        let mut text = TextMut::new();
+        text.extend(iter);
+        text
+    }
+}
+
+// ## Serde
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TextMut {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TextMut {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TextMutVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for TextMutVisitor {
+            type Value = TextMut;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<TextMut, E> {
+                Ok(TextMut::copy_from(v))
+            }
+        }
+
+        deserializer.deserialize_str(TextMutVisitor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -746,4 +1696,640 @@ mod tests {
         buf.clear();
         assert_eq!(buf, "");
     }
+
+    #[test]
+    fn text_mut_cmp_owning_str_pointers() {
+        let text = TextMut::copy_from("héllo");
+        assert_eq!(text, Box::<str>::from("héllo"));
+        assert_eq!(text, Rc::<str>::from("héllo"));
+        assert_eq!(text, Arc::<str>::from("héllo"));
+        assert_ne!(text, Box::<str>::from("hello"));
+        let world: Arc<str> = "world".into();
+        assert!(text < world);
+        let abc: Rc<str> = "abc".into();
+        assert!(text > abc);
+    }
+
+    #[test]
+    fn text_mut_from_utf8_lossy() {
+        let valid = BytesMut::from("valid ✓");
+        let ptr = valid.as_ptr();
+        let text = TextMut::from_utf8_lossy(valid);
+        assert_eq!(text, "valid ✓");
+        assert_eq!(text.as_bytes().as_ptr(), ptr);
+
+        let invalid = BytesMut::from(&b"a\xffb\xe2\x9c"[..]);
+        let mut text = TextMut::from_utf8_lossy(invalid);
+        assert_eq!(text, "a\u{FFFD}b\u{FFFD}");
+        text.push('!');
+        assert_eq!(text, "a\u{FFFD}b\u{FFFD}!");
+    }
+
+    #[test]
+    fn text_mut_truncate_chars() {
+        let mut text = TextMut::copy_from("a🦀é🦀b");
+        text.truncate_chars(10);
+        assert_eq!(text, "a🦀é🦀b");
+        text.truncate_chars(5);
+        assert_eq!(text, "a🦀é🦀b");
+        text.truncate_chars(2);
+        assert_eq!(text, "a🦀");
+        assert_eq!(text.len(), 5);
+        text.truncate_chars(0);
+        assert_eq!(text, "");
+    }
+
+    #[test]
+    fn text_mut_write_fmt() {
+        let mut buf = TextMut::new();
+        write!(buf, "{}: {:?}", 42, "héllo").unwrap();
+        buf.write_char('!').unwrap();
+        assert_eq!(buf, "42: \"héllo\"!");
+
+        let (x, y) = (1.5, "🦀");
+        assert_eq!(crate::text!("{}-{:>4}", x, y), format!("{}-{:>4}", x, y));
+        assert_eq!(crate::text!("plain"), "plain");
+    }
+
+    #[test]
+    fn text_mut_split_at_char() {
+        let text = TextMut::copy_from("🦀é€x");
+        let text = text.split_at_char(5).unwrap_err();
+        let (mut a, mut b) = text.split_at_char(2).unwrap();
+        assert_eq!(a, "🦀é");
+        assert_eq!(b, "€x");
+
+        // both halves can be modified independently
+        a.make_ascii_uppercase();
+        b.make_ascii_uppercase();
+        assert_eq!(a, "🦀é");
+        assert_eq!(b, "€X");
+
+        // and rejoined without copying
+        let ptr = a.as_ptr();
+        let joined = a.join(b);
+        assert_eq!(joined, "🦀é€X");
+        assert_eq!(joined.as_ptr(), ptr);
+
+        let (a, b) = TextMut::copy_from("ab").split_at_char(2).unwrap();
+        assert_eq!(a, "ab");
+        assert_eq!(b, "");
+    }
+
+    #[test]
+    fn text_mut_prepend() {
+        let mut text = TextMut::new();
+        text.prepend_str("");
+        assert_eq!(text, "");
+        text.prepend_str("wörld");
+        assert_eq!(text, "wörld");
+        text.prepend(' ');
+        text.prepend_str("héllo,");
+        text.prepend('🦀');
+        assert_eq!(text, "🦀héllo, wörld");
+
+        let mut text = TextMut::new();
+        text.prepend('€');
+        assert_eq!(text, "€");
+    }
+
+    #[test]
+    fn text_mut_take_frozen_to() {
+        let mut buf = TextMut::with_capacity(64);
+        let mut frames = Vec::new();
+        for chunk in ["héllo\nwo", "rld\n", "🦀", "\npartial"] {
+            buf.push_str(chunk);
+            while let Some(i) = buf.find('\n') {
+                frames.push(buf.take_frozen_to(i + 1).unwrap());
+            }
+        }
+        assert_eq!(frames, ["héllo\n", "world\n", "🦀\n"]);
+        assert_eq!(buf, "partial");
+
+        let mut buf = TextMut::copy_from("é");
+        assert!(buf.take_frozen_to(1).is_none());
+        assert!(buf.take_frozen_to(3).is_none());
+        assert_eq!(buf, "é");
+    }
+
+    #[test]
+    fn text_mut_push_display() {
+        let mut text = TextMut::new();
+        text.push_display(&-17);
+        text.push(' ');
+        text.push_display(&2.5f64);
+        text.push(' ');
+        text.push_display("🦀");
+        text.push(' ');
+        text.push_display(&Text::from("text"));
+        assert_eq!(text, "-17 2.5 🦀 text");
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn text_mut_insert_at_grapheme() {
+        // `e` followed by a combining acute accent is a single grapheme, so
+        // nothing can be inserted between them
+        let mut text = TextMut::copy_from("e\u{301}x");
+        assert!(text.insert_at_grapheme(1, "[]"));
+        assert_eq!(text, "e\u{301}[]x");
+
+        let mut text = TextMut::copy_from("e\u{301}x");
+        assert!(text.insert_at_grapheme(0, "[]"));
+        assert_eq!(text, "[]e\u{301}x");
+
+        let mut text = TextMut::copy_from("👍🏽!");
+        assert!(text.insert_at_grapheme(1, "?"));
+        assert_eq!(text, "👍🏽?!");
+        assert!(text.insert_at_grapheme(3, "."));
+        assert_eq!(text, "👍🏽?!.");
+        assert!(!text.insert_at_grapheme(5, "."));
+        assert_eq!(text, "👍🏽?!.");
+
+        let mut empty = TextMut::new();
+        assert!(empty.insert_at_grapheme(0, "hi"));
+        assert_eq!(empty, "hi");
+    }
+
+    #[test]
+    fn text_mut_reserve_amortized() {
+        let mut text = TextMut::with_capacity(16);
+        text.push_str("0123456789abcdef");
+
+        let mut capacity = text.capacity();
+        let mut reallocations = 0;
+        for _ in 0..1000 {
+            text.reserve_amortized(1);
+            if text.capacity() != capacity {
+                assert!(text.capacity() >= capacity * 2);
+                capacity = text.capacity();
+                reallocations += 1;
+            }
+            text.push('x');
+        }
+        assert!(reallocations <= 7);
+
+        // doesn't grow when there's already room
+        let mut text = TextMut::with_capacity(32);
+        text.reserve_amortized(32);
+        assert_eq!(text.capacity(), 32);
+
+        // grows to fit a large request even past double the capacity
+        text.reserve_amortized(100);
+        assert!(text.capacity() >= 100);
+    }
+
+    #[test]
+    fn text_mut_reserve_and_fill() {
+        let mut text = TextMut::copy_from("año: ");
+        text.reserve_and_fill(8, |buf| {
+            assert_eq!(buf.len(), 8);
+            buf[..5].copy_from_slice(b"2024 ");
+            5
+        })
+        .unwrap();
+        assert_eq!(text, "año: 2024 ");
+
+        // writing nothing is fine
+        text.reserve_and_fill(4, |_| 0).unwrap();
+        assert_eq!(text, "año: 2024 ");
+
+        // invalid bytes
+        let err = text
+            .reserve_and_fill(4, |buf| {
+                buf[..3].copy_from_slice(b"a\xffb");
+                3
+            })
+            .unwrap_err();
+        assert_eq!(err.valid_up_to(), 1);
+        assert_eq!(text, "año: 2024 ");
+
+        // a char cut off at the end of the written bytes
+        let check = "✓".as_bytes();
+        assert!(text
+            .reserve_and_fill(4, |buf| {
+                buf[..2].copy_from_slice(&check[..2]);
+                2
+            })
+            .is_err());
+        assert_eq!(text, "año: 2024 ");
+
+        // finishing it off is fine
+        text.reserve_and_fill(4, |buf| {
+            buf[..3].copy_from_slice(check);
+            3
+        })
+        .unwrap();
+        assert_eq!(text, "año: 2024 ✓");
+    }
+
+    #[test]
+    fn text_mut_reserve_and_fill_panic() {
+        let mut text = TextMut::copy_from("safe");
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            text.reserve_and_fill(4, |buf| {
+                buf[0] = 0xff;
+                panic!("oops");
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(text, "safe");
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            text.reserve_and_fill(4, |_| 5)
+        }));
+        assert!(result.is_err());
+        assert_eq!(text, "safe");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn text_mut_serde() {
+        for s in ["", "héllo, wörld 🌍", r#"say "hi" \ bye"#, "line\nbreak"] {
+            let text = TextMut::copy_from(s);
+            let json = serde_json::to_string(&text).unwrap();
+            assert_eq!(json, serde_json::to_string(s).unwrap());
+            let back: TextMut = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, s);
+        }
+
+        assert!(serde_json::from_str::<TextMut>("null").is_err());
+    }
+
+    #[test]
+    fn text_mut_make_ascii_titlecase() {
+        let mut text = TextMut::copy_from("hELLO   wORLD\tand\nmORE");
+        text.make_ascii_titlecase();
+        assert_eq!(text, "Hello   World\tAnd\nMore");
+
+        let mut text = TextMut::copy_from("\"quoted\" ...dots 'single' 42nd");
+        text.make_ascii_titlecase();
+        assert_eq!(text, "\"Quoted\" ...Dots 'Single' 42Nd");
+
+        // non-ASCII chars are left alone, and don't count as the first letter
+        let mut text = TextMut::copy_from("ÉCOLE über straße");
+        text.make_ascii_titlecase();
+        assert_eq!(text, "ÉCole üBer Straße");
+
+        let mut empty = TextMut::new();
+        empty.make_ascii_titlecase();
+        assert_eq!(empty, "");
+    }
+
+    #[test]
+    fn text_mut_drain_text() {
+        let mut text = TextMut::copy_from("añob→c");
+        let removed = text.drain_text(3..4).unwrap();
+        assert_eq!(removed, "o");
+        assert_eq!(text, "añb→c");
+
+        assert_eq!(text.drain_text(4..=6).unwrap(), "→");
+        assert_eq!(text, "añbc");
+
+        // not char boundaries, or out of range
+        assert!(text.drain_text(2..3).is_none());
+        assert!(text.drain_text(..2).is_none());
+        assert!(text.drain_text(4..10).is_none());
+        let (start, end) = (3, 1);
+        assert!(text.drain_text(start..end).is_none());
+        assert_eq!(text, "añbc");
+
+        assert_eq!(text.drain_text(4..4).unwrap(), "");
+        assert_eq!(text.drain_text(..).unwrap(), "añbc");
+        assert_eq!(text, "");
+    }
+
+    #[test]
+    fn text_mut_insert() {
+        let mut text = TextMut::copy_from("ñb");
+        assert!(text.insert(0, 'a'));
+        assert_eq!(text, "añb");
+        assert!(text.insert(text.len(), 'c'));
+        assert_eq!(text, "añbc");
+        assert!(text.insert(3, '🌍'));
+        assert_eq!(text, "añ🌍bc");
+
+        // inside `ñ`, and past the end
+        assert!(!text.insert(2, 'x'));
+        assert!(!text.insert(text.len() + 1, 'x'));
+        assert_eq!(text, "añ🌍bc");
+    }
+
+    #[test]
+    fn text_mut_insert_str() {
+        let mut text = TextMut::copy_from("middle");
+        assert!(text.insert_str(0, "start "));
+        assert!(text.insert_str(text.len(), " end"));
+        assert_eq!(text, "start middle end");
+        assert!(text.insert_str(6, "🎉"));
+        assert_eq!(text, "start 🎉middle end");
+        assert!(text.insert_str(3, ""));
+        assert_eq!(text, "start 🎉middle end");
+
+        assert!(!text.insert_str(7, "x"));
+        assert!(!text.insert_str(100, "x"));
+        assert_eq!(text, "start 🎉middle end");
+
+        let mut empty = TextMut::new();
+        assert!(empty.insert_str(0, "é"));
+        assert_eq!(empty, "é");
+    }
+
+    #[test]
+    fn text_mut_truncate() {
+        let mut text = TextMut::with_capacity(32);
+        text.push_str("a€b");
+        assert!(text.truncate(5));
+        assert_eq!(text, "a€b");
+
+        assert!(!text.truncate(2));
+        assert_eq!(text, "a€b");
+
+        assert!(text.truncate(4));
+        assert_eq!(text, "a€");
+        assert!(text.truncate(1));
+        assert_eq!(text, "a");
+        assert_eq!(text.capacity(), 32);
+    }
+
+    #[test]
+    fn text_mut_from_iter() {
+        let mut text: TextMut = "héllo".chars().filter(|c| *c != 'l').collect();
+        assert_eq!(text, "héo");
+        text.push('!');
+        assert_eq!(text, "héo!");
+
+        let text: TextMut = "a b c".split(' ').collect();
+        assert_eq!(text, "abc");
+
+        let text: TextMut = vec![String::from("x"), String::from("ÿ")]
+            .into_iter()
+            .collect();
+        assert_eq!(text, "xÿ");
+    }
+
+    #[test]
+    fn text_mut_writeln_str() {
+        let mut text = TextMut::new();
+        text.writeln_str("one");
+        text.writeln_str("twö");
+        assert_eq!(text, "one\ntwö\n");
+
+        let mut text = TextMut::new();
+        text.set_line_ending(LineEnding::CrLf);
+        text.writeln_str("EHLO example.com");
+        text.writeln_str("QUIT");
+        assert_eq!(text, "EHLO example.com\r\nQUIT\r\n");
+
+        // split halves keep the line ending
+        let mut end = text.split_off(6).unwrap();
+        assert_eq!(end.line_ending(), LineEnding::CrLf);
+        end.writeln_str(".");
+        assert!(end.ends_with("QUIT\r\n.\r\n"));
+    }
+
+    #[test]
+    fn text_mut_make_ascii_case() {
+        let mut text = TextMut::copy_from("HeLLo, ÀÉÎ wörld ß");
+        text.make_ascii_lowercase();
+        assert_eq!(text, "hello, ÀÉÎ wörld ß");
+        text.make_ascii_uppercase();
+        assert_eq!(text, "HELLO, ÀÉÎ WöRLD ß");
+
+        assert!(text.eq_ignore_ascii_case("hello, ÀÉÎ wöRLD ß"));
+        // non-ASCII letters have to match exactly
+        assert!(!text.eq_ignore_ascii_case("hello, àéî wörld ß"));
+        assert!(!text.eq_ignore_ascii_case("hello"));
+    }
+
+    #[test]
+    fn text_mut_from_utf8_error_returns_bytes() {
+        let buf = BytesMut::from(&b"ok\xe2\x82"[..]);
+        let ptr = buf.as_ptr();
+        let err = TextMut::from_utf8(buf).unwrap_err();
+        assert_eq!(err.utf8_error().valid_up_to(), 2);
+        assert_eq!(err.utf8_error().error_len(), None);
+        let back = err.into_bytes();
+        assert_eq!(&back[..], b"ok\xe2\x82");
+        assert_eq!(back.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn text_mut_remove() {
+        let mut text = TextMut::copy_from("ab🦀cd");
+        assert_eq!(text.remove(0), Some('a'));
+        assert_eq!(text, "b🦀cd");
+        assert_eq!(text.remove(1), Some('🦀'));
+        assert_eq!(text, "bcd");
+        assert_eq!(text.remove(2), Some('d'));
+        assert_eq!(text, "bc");
+
+        let mut text = TextMut::copy_from("é🦀");
+        assert_eq!(text.remove(1), None);
+        assert_eq!(text.remove(3), None);
+        assert_eq!(text.remove(6), None);
+        assert_eq!(text.remove(7), None);
+        assert_eq!(text, "é🦀");
+        assert_eq!(text.remove(2), Some('🦀'));
+        assert_eq!(text.remove(0), Some('é'));
+        assert!(text.is_empty());
+    }
+
+    #[test]
+    fn text_mut_retain() {
+        let mut text = TextMut::copy_from(" a b\tc\n🦀 é ");
+        let capacity = text.capacity();
+        text.retain(|c| !c.is_whitespace());
+        assert_eq!(text, "abc🦀é");
+        assert_eq!(text.capacity(), capacity);
+
+        let mut text = TextMut::copy_from("keep 🦀 all");
+        text.retain(|_| true);
+        assert_eq!(text, "keep 🦀 all");
+
+        let mut text = TextMut::copy_from("drop 🦀 all");
+        text.retain(|_| false);
+        assert!(text.is_empty());
+
+        let mut text = TextMut::copy_from("ab🦀cd");
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            text.retain(|c| {
+                assert!(c != 'c');
+                c != 'b'
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(text, "a🦀");
+    }
+
+    #[test]
+    fn text_mut_drain() {
+        let mut text = TextMut::copy_from("ab🦀cd");
+        assert_eq!(text.drain(2..6).unwrap(), "🦀");
+        assert_eq!(text, "abcd");
+
+        let mut text = TextMut::copy_from("éabc");
+        assert_eq!(text.drain(..2).unwrap(), "é");
+        assert_eq!(text, "abc");
+
+        let mut text = TextMut::copy_from("abc🦀");
+        let len = text.len();
+        assert_eq!(text.drain(1..len).unwrap(), "bc🦀");
+        assert_eq!(text, "a");
+
+        let mut text = TextMut::copy_from("abc🦀");
+        assert_eq!(text.drain(3..).unwrap(), "🦀");
+        assert_eq!(text, "abc");
+        assert!(text.drain(1..5).is_none());
+        assert_eq!(text.drain(..).unwrap(), "abc");
+        assert!(text.is_empty());
+
+        let mut text = TextMut::copy_from("a🦀");
+        assert!(text.drain(2..).is_none());
+        assert!(text.drain(..3).is_none());
+        assert_eq!(text, "a🦀");
+    }
+
+    #[test]
+    fn text_mut_from_box_str() {
+        let boxed: Box<str> = "boxed 🦀".into();
+        let mut text = TextMut::from(boxed);
+        text.push('!');
+        assert_eq!(text, "boxed 🦀!");
+    }
+
+    #[test]
+    fn text_mut_try_join() {
+        let mut text = TextMut::copy_from("añb🦀c");
+        let ptr = text.as_ptr();
+        let capacity = text.capacity();
+        let mut rest = text.split_off(1).unwrap();
+        let mut rest2 = rest.split_off(3).unwrap();
+        let rest3 = rest2.split_off(4).unwrap();
+        assert!(text.try_join(rest).is_ok());
+        assert!(text.try_join(rest2).is_ok());
+        assert!(text.try_join(rest3).is_ok());
+        assert_eq!(text, "añb🦀c");
+        assert_eq!(text.as_ptr(), ptr);
+        assert_eq!(text.capacity(), capacity);
+
+        // out of order pieces aren't contiguous
+        let mut text = TextMut::copy_from("abc");
+        let mut b = text.split_off(1).unwrap();
+        let c = b.split_off(1).unwrap();
+        let c = text.try_join(c).unwrap_err();
+        assert_eq!(text, "a");
+        assert_eq!(c, "c");
+
+        let mut text = TextMut::copy_from("ab");
+        assert!(text.try_join(TextMut::new()).is_ok());
+        let other = text.try_join(TextMut::copy_from("cd")).unwrap_err();
+        assert_eq!(other, "cd");
+        assert_eq!(text, "ab");
+    }
+
+    #[test]
+    fn text_mut_extend_text_mut() {
+        let mut text = TextMut::copy_from("one two three");
+        let ptr = text.as_ptr();
+        let capacity = text.capacity();
+        let mut second = text.split_off(3).unwrap();
+        let third = second.split_off(4).unwrap();
+        text.extend(vec![second, third]);
+        assert_eq!(text, "one two three");
+        assert_eq!(text.as_ptr(), ptr);
+        assert_eq!(text.capacity(), capacity);
+
+        let mut text = TextMut::copy_from("x");
+        text.extend(vec![TextMut::copy_from("y🦀"), TextMut::copy_from("z")]);
+        assert_eq!(text, "xy🦀z");
+    }
+
+    #[test]
+    fn text_mut_get_mut() {
+        let mut text = TextMut::copy_from("héllo wörld");
+        let middle = text.get_mut(1..6).unwrap();
+        assert_eq!(middle, "éllo");
+        middle.make_ascii_uppercase();
+        // non-ASCII chars are left alone
+        assert_eq!(text, "héLLO wörld");
+
+        assert!(text.get_mut(2..4).is_none());
+        assert!(text.get_mut(..2).is_none());
+        let (start, end) = (5, 3);
+        assert!(text.get_mut(start..end).is_none());
+        assert!(text.get_mut(..100).is_none());
+        let len = text.len();
+        assert_eq!(text.get_mut(len..).unwrap(), "");
+        assert_eq!(text.get_mut(..).unwrap().len(), len);
+    }
+
+    #[test]
+    fn text_mut_reserve_exact_and_shrink_to_fit() {
+        let mut text = TextMut::copy_from("añb");
+        text.reserve_exact(7);
+        assert_eq!(text.capacity(), 11);
+        assert_eq!(text, "añb");
+        // already has room
+        text.reserve_exact(3);
+        assert_eq!(text.capacity(), 11);
+
+        text.push_str("🦀");
+        text.shrink_to_fit();
+        assert_eq!(text.capacity(), text.len());
+        assert_eq!(text, "añb🦀");
+        text.push('!');
+        assert_eq!(text, "añb🦀!");
+
+        let mut empty = TextMut::with_capacity(64);
+        empty.shrink_to_fit();
+        assert_eq!(empty.capacity(), 0);
+    }
+
+    #[test]
+    fn text_mut_push_text() {
+        let mut text = TextMut::copy_from("first half, second half");
+        let ptr = text.as_ptr();
+        let capacity = text.capacity();
+        let second = text.split_off(11).unwrap();
+        text.push_text(second);
+        assert_eq!(text, "first half, second half");
+        assert_eq!(text.as_ptr(), ptr);
+        assert_eq!(text.capacity(), capacity);
+
+        let mut text = TextMut::copy_from("añb");
+        let other = TextMut::copy_from("🦀c");
+        let other_ptr = other.as_ptr();
+        text.push_text(other);
+        assert_eq!(text, "añb🦀c");
+        assert_ne!(text[4..].as_ptr(), other_ptr);
+    }
+
+    #[test]
+    fn text_mut_into_string() {
+        // uniquely owned, so the buffer is reused
+        let text = TextMut::copy_from("unique ✓");
+        let ptr = text.as_ptr();
+        let string = text.into_string();
+        assert_eq!(string, "unique ✓");
+        assert_eq!(string.as_ptr(), ptr);
+
+        // shares its buffer with the other half, so it has to be copied
+        let mut text = TextMut::copy_from("shared ✓ buffer");
+        let other = text.split_off(10).unwrap();
+        let ptr = text.as_ptr();
+        let string = text.into_string();
+        assert_eq!(string, "shared ✓");
+        assert_ne!(string.as_ptr(), ptr);
+        assert_eq!(other, " buffer");
+
+        // once the other half is gone, the buffer can be reused again
+        let mut text = TextMut::copy_from("front back");
+        let front = text.split_to(6).unwrap();
+        let start = front.as_ptr();
+        drop(front);
+        let string = text.into_string();
+        assert_eq!(string, "back");
+        assert_eq!(string.as_ptr(), start);
+    }
 }